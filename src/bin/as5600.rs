@@ -0,0 +1,42 @@
+//! `as5600`: inspect and configure an AS5600 on a Linux I2C bus.
+//!
+//! Run `as5600 --help` for the available commands.
+
+use AS5600_Driver::cli::{self, CliError};
+#[cfg(feature = "mock")]
+use AS5600_Driver::mock::{AS56Mock, MagnetModel};
+use linux_embedded_hal::I2cdev;
+use std::io;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let result = cli::parse_args(std::env::args().skip(1)).and_then(|options| {
+        let mut input = io::stdin().lock();
+        let mut prompt = io::stderr().lock();
+        let mut out = io::stdout().lock();
+        #[cfg(feature = "mock")]
+        if options.mock {
+            let mock = if options.as5600l {
+                AS56Mock::new_as5600l()
+            } else {
+                AS56Mock::new()
+            };
+            mock.mock_set_magnet(MagnetModel::default());
+            return cli::run(mock, &options, &mut input, &mut prompt, &mut out);
+        }
+        let i2c = I2cdev::new(&options.bus)
+            .map_err(|e| CliError::Io(io::Error::other(format!("{}: {}", options.bus, e))))?;
+        cli::run(i2c, &options, &mut input, &mut prompt, &mut out)
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("as5600: {}", e);
+            if let CliError::Usage(_) = e {
+                eprintln!("\n{}", cli::USAGE);
+            }
+            ExitCode::from(e.exit_code() as u8)
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::error::AS56Error;
+use crate::traits::AS5600Interface;
+use crate::types::*;
+
+/// Shadow copies of the programming registers, `None` until read or written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Shadow {
+    zpos: Option<u16>,
+    mpos: Option<u16>,
+    mang: Option<u16>,
+    config: Option<Configuration>,
+}
+
+/// Register cache built on top of any [`AS5600Interface`].
+///
+/// Keeps shadow copies of the programming registers (ZPOS, MPOS, MANG and CONF),
+/// which only change when written, so reading them costs no bus traffic after the
+/// first time. Writes through the wrapper update the shadow copies. The live
+/// registers (STATUS, RAW_ANGLE, ANGLE, AGC, MAGNITUDE) and ZMCO are always read
+/// from the device.
+///
+/// Anything that changes the programming registers behind the wrapper's back (a
+/// write through [`sensor`](Self::sensor), an OTP reload or burn, a power cycle)
+/// leaves stale copies; call [`invalidate`](Self::invalidate) or
+/// [`refresh`](Self::refresh) afterwards.
+pub struct Cached<S> {
+    sensor: S,
+    shadow: Shadow,
+}
+
+impl<S: AS5600Interface> Cached<S> {
+    /// Wraps a sensor with an empty cache.
+    pub fn new(sensor: S) -> Self {
+        Self {
+            sensor,
+            shadow: Shadow::default(),
+        }
+    }
+
+    /// Drops all shadow copies, so the next read of each register goes to the device.
+    pub fn invalidate(&mut self) {
+        self.shadow = Shadow::default();
+    }
+
+    /// Re-reads all programming registers from the device into the cache.
+    ///
+    /// On error the cache is left empty.
+    pub fn refresh(&mut self) -> Result<(), AS56Error<S::Error>> {
+        self.invalidate();
+        self.shadow = Shadow {
+            zpos: Some(self.sensor.get_zero_position()?),
+            mpos: Some(self.sensor.get_max_position()?),
+            mang: Some(self.sensor.get_max_angle()?),
+            config: Some(self.sensor.get_config()?),
+        };
+        Ok(())
+    }
+
+    /// Returns `true` if all programming registers are cached.
+    pub fn is_complete(&self) -> bool {
+        let s = &self.shadow;
+        s.zpos.is_some() && s.mpos.is_some() && s.mang.is_some() && s.config.is_some()
+    }
+
+    /// Returns a mutable reference to the underlying sensor.
+    ///
+    /// Writes made through it bypass the cache.
+    pub fn sensor(&mut self) -> &mut S {
+        &mut self.sensor
+    }
+
+    /// Consumes the wrapper and returns the underlying sensor.
+    pub fn release(self) -> S {
+        self.sensor
+    }
+}
+
+/// Returns the cached value, or reads it and caches it.
+fn cached<T: Copy, E>(
+    slot: &mut Option<T>,
+    read: impl FnOnce() -> Result<T, AS56Error<E>>,
+) -> Result<T, AS56Error<E>> {
+    match *slot {
+        Some(value) => Ok(value),
+        None => {
+            let value = read()?;
+            *slot = Some(value);
+            Ok(value)
+        }
+    }
+}
+
+/// Caches the written value, or forgets the slot if the write failed part-way.
+fn written<T, E>(
+    slot: &mut Option<T>,
+    value: T,
+    result: Result<(), AS56Error<E>>,
+) -> Result<(), AS56Error<E>> {
+    match result {
+        Ok(()) => *slot = Some(value),
+        // A rejected argument never reaches the bus, the register is unchanged.
+        Err(AS56Error::OutOfRange { .. }) => {}
+        Err(_) => *slot = None,
+    }
+    result
+}
+
+impl<S: AS5600Interface> AS5600Interface for Cached<S> {
+    type Error = S::Error;
+
+    fn read_raw_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.read_raw_angle()
+    }
+
+    fn read_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.read_angle()
+    }
+
+    fn get_magnet_status(&mut self) -> Result<MagnetStatus, AS56Error<Self::Error>> {
+        self.sensor.get_magnet_status()
+    }
+
+    fn get_status_raw(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_status_raw()
+    }
+
+    fn get_magnitude(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.get_magnitude()
+    }
+
+    fn get_agc(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_agc()
+    }
+
+    fn get_burn_count(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_burn_count()
+    }
+
+    fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        self.sensor.read_snapshot()
+    }
+
+    fn get_config(&mut self) -> Result<Configuration, AS56Error<Self::Error>> {
+        cached(&mut self.shadow.config, || self.sensor.get_config())
+    }
+
+    fn set_config(&mut self, config: Configuration) -> Result<(), AS56Error<Self::Error>> {
+        let result = self.sensor.set_config(config);
+        written(&mut self.shadow.config, config, result)
+    }
+
+    fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>> {
+        match self.shadow.config {
+            Some(current) => {
+                let mut config = current;
+                f(&mut config);
+                if config == current {
+                    return Ok(());
+                }
+                // The shadow copy holds the unused bits too, so one write is enough.
+                let result = self.sensor.set_config(config);
+                written(&mut self.shadow.config, config, result)
+            }
+            None => {
+                let mut applied = None;
+                self.sensor.modify_config(|c| {
+                    f(c);
+                    applied = Some(*c);
+                })?;
+                self.shadow.config = applied;
+                Ok(())
+            }
+        }
+    }
+
+    fn get_zero_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        cached(&mut self.shadow.zpos, || self.sensor.get_zero_position())
+    }
+
+    fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        let result = self.sensor.set_zero_position(angle);
+        written(&mut self.shadow.zpos, angle, result)
+    }
+
+    fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        cached(&mut self.shadow.mpos, || self.sensor.get_max_position())
+    }
+
+    fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        let result = self.sensor.set_max_position(angle);
+        written(&mut self.shadow.mpos, angle, result)
+    }
+
+    fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        cached(&mut self.shadow.mang, || self.sensor.get_max_angle())
+    }
+
+    fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        let result = self.sensor.set_max_angle(angle);
+        written(&mut self.shadow.mang, angle, result)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::AS5600Driver;
+    use crate::mock::{AS56Mock, Direction, FaultAction};
+    use crate::regs::regs;
+
+    fn setup() -> (AS56Mock, Cached<AS5600Driver<AS56Mock>>) {
+        let mock = AS56Mock::new();
+        (mock.clone(), Cached::new(AS5600Driver::new(mock)))
+    }
+
+    /// Counts the logged writes touching `reg`.
+    fn writes(mock: &AS56Mock, reg: u8) -> usize {
+        mock.mock_log()
+            .iter()
+            .filter(|entry| entry.direction == Direction::Write && entry.covers(reg))
+            .count()
+    }
+
+    #[test]
+    fn programming_registers_are_read_once() {
+        let (mock, mut cached) = setup();
+        for _ in 0..3 {
+            assert_eq!(cached.get_zero_position().unwrap(), 0);
+            assert_eq!(cached.get_max_position().unwrap(), 0);
+            assert_eq!(cached.get_max_angle().unwrap(), 0);
+            assert!(cached.get_config().unwrap().watchdog);
+            cached.read_raw_angle().unwrap();
+        }
+        for reg in [regs::ZPOS_HI, regs::MPOS_HI, regs::MANG_HI, regs::CONF_HI] {
+            mock.mock_assert_reads(reg, 1);
+        }
+        // Live registers always go to the device.
+        mock.mock_assert_reads(regs::RAW_ANGLE_HI, 3);
+        assert!(cached.is_complete());
+    }
+
+    #[test]
+    fn writes_update_the_shadow_copy() {
+        let (mock, mut cached) = setup();
+        cached.set_zero_position(100).unwrap();
+        cached.set_max_position(3000).unwrap();
+        cached.set_max_angle(2048).unwrap();
+        let config = Configuration {
+            power_mode: PowerMode::LPM3,
+            ..Configuration::default()
+        };
+        cached.set_config(config).unwrap();
+        mock.mock_clear_log();
+
+        assert_eq!(cached.get_zero_position().unwrap(), 100);
+        assert_eq!(cached.get_max_position().unwrap(), 3000);
+        assert_eq!(cached.get_max_angle().unwrap(), 2048);
+        assert_eq!(cached.get_config().unwrap(), config);
+        assert!(mock.mock_log().is_empty());
+    }
+
+    #[test]
+    fn failed_writes_keep_or_drop_the_slot() {
+        let (mock, mut cached) = setup();
+        cached.set_zero_position(100).unwrap();
+
+        // Rejected before reaching the bus: the cached value is still right.
+        let err = cached.set_zero_position(5000).unwrap_err();
+        assert!(matches!(err, AS56Error::OutOfRange { .. }));
+        mock.mock_clear_log();
+        assert_eq!(cached.get_zero_position().unwrap(), 100);
+        mock.mock_assert_reads(regs::ZPOS_HI, 0);
+
+        // A bus error leaves the register unknown, so it is read again.
+        mock.mock_fail_nth(1, FaultAction::NACK_DATA);
+        cached.set_zero_position(200).unwrap_err();
+        assert_eq!(cached.get_zero_position().unwrap(), 100);
+        mock.mock_assert_reads(regs::ZPOS_HI, 1);
+    }
+
+    #[test]
+    fn invalidate_and_refresh_reload_the_cache() {
+        let (mock, mut cached) = setup();
+        cached.get_zero_position().unwrap();
+        cached.sensor().set_zero_position(300).unwrap();
+        assert_eq!(cached.get_zero_position().unwrap(), 0);
+        cached.invalidate();
+        assert!(!cached.is_complete());
+        assert_eq!(cached.get_zero_position().unwrap(), 300);
+
+        cached.sensor().set_max_angle(1000).unwrap();
+        cached.refresh().unwrap();
+        assert!(cached.is_complete());
+        mock.mock_clear_log();
+        assert_eq!(cached.get_max_angle().unwrap(), 1000);
+        assert!(mock.mock_log().is_empty());
+
+        // A failed refresh leaves the cache empty.
+        mock.mock_fail_nth(2, FaultAction::NACK_ADDRESS);
+        cached.refresh().unwrap_err();
+        assert!(!cached.is_complete());
+    }
+
+    #[test]
+    fn modify_config_skips_unchanged_writes() {
+        let (mock, mut cached) = setup();
+        // Uncached: one read-modify-write, which fills the cache.
+        cached
+            .modify_config(|c| c.power_mode = PowerMode::LPM1)
+            .unwrap();
+        mock.mock_assert_reads(regs::CONF_HI, 1);
+        assert_eq!(writes(&mock, regs::CONF_HI), 1);
+
+        mock.mock_clear_log();
+        cached
+            .modify_config(|c| c.power_mode = PowerMode::LPM1)
+            .unwrap();
+        cached.set_power_mode(PowerMode::LPM1).unwrap();
+        assert!(mock.mock_log().is_empty());
+
+        // Cached: a change is a single write.
+        cached.set_watchdog(false).unwrap();
+        mock.mock_assert_reads(regs::CONF_HI, 0);
+        assert_eq!(writes(&mock, regs::CONF_HI), 1);
+        let config = cached.sensor().get_config().unwrap();
+        assert_eq!(
+            (config.power_mode, config.watchdog),
+            (PowerMode::LPM1, false)
+        );
+    }
+}
@@ -0,0 +1,967 @@
+//! Command-line tool for inspecting and configuring an AS5600 (behind the `cli` feature).
+//!
+//! The `as5600` binary only opens the bus and calls [`run`], so the whole tool can
+//! be exercised against the mock (`--mock`, built with the `cli-mock` feature).
+//! This module is an implementation detail of the binary and may change without
+//! notice.
+
+use crate::driver::{check_angle_burn, check_setting_burn};
+use crate::regs::*;
+use crate::traits::AS5600Interface;
+use crate::types::*;
+use crate::units::{Angle, RawCounts};
+use crate::{AS56Error, AS5600Driver};
+use core::fmt;
+use core::time::Duration;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use std::format;
+use std::io::{self, BufRead, Write};
+use std::string::{String, ToString};
+use std::thread;
+use std::vec::Vec;
+
+/// I2C bus used when `--bus` is not given.
+pub const DEFAULT_BUS: &str = "/dev/i2c-1";
+
+/// Usage text printed by `--help` and after argument errors.
+pub const USAGE: &str = "\
+Usage: as5600 [OPTIONS] <COMMAND>
+
+Commands:
+  read                     Read RAW_ANGLE and ANGLE
+  watch                    Read continuously (--interval MS, --count N)
+  status                   Magnet status, AGC, magnitude and burn count
+  config get               Show the CONF register fields
+  config set KEY=VALUE...  Change CONF fields:
+                             power-mode=nom|lpm1|lpm2|lpm3  hysteresis=off|1|2|3
+                             output=analog|analog-reduced|pwm  pwm-freq=115|230|460|920
+                             slow-filter=16x|8x|4x|2x  fast-filter=off|6|7|9|10|18|21|24
+                             watchdog=on|off
+  zero                     Set ZPOS to the current raw angle
+  range                    Set the output range (--mpos COUNTS|here, --mang COUNTS)
+  dump                     Print all registers as hex
+  burn angle|settings      Permanently burn ZPOS/MPOS or MANG/CONF to OTP
+                           (asks for confirmation unless --yes, --dry-run only checks)
+
+Options:
+  --bus PATH        I2C bus device [default: /dev/i2c-1]
+  --address ADDR    Sensor address, decimal or 0x.. [default: 0x36, 0x40 with --as5600l]
+  --as5600l         The sensor is an AS5600L (dump adds I2C_ADDR and I2C_UPDT)
+  --json            Print JSON instead of text
+  --mock            Use the built-in AS5600 emulator instead of a bus
+                    (only in builds with the cli-mock feature)
+  -h, --help        Print this help";
+
+/// Errors of the command-line tool.
+#[derive(Debug)]
+pub enum CliError {
+    /// Invalid command line.
+    Usage(String),
+    /// The sensor reported an error.
+    Sensor(String),
+    /// Reading input or writing output failed.
+    Io(io::Error),
+    /// A burn was not confirmed.
+    Aborted,
+}
+
+impl CliError {
+    /// Returns the process exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "{}", msg),
+            CliError::Sensor(msg) => write!(f, "sensor error: {}", msg),
+            CliError::Io(e) => write!(f, "I/O error: {}", e),
+            CliError::Aborted => write!(f, "aborted, nothing was burned"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::Io(e)
+    }
+}
+
+impl<E: fmt::Debug> From<AS56Error<E>> for CliError {
+    fn from(e: AS56Error<E>) -> Self {
+        CliError::Sensor(e.to_string())
+    }
+}
+
+/// A subcommand with its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Print the usage text.
+    Help,
+    /// Read RAW_ANGLE and ANGLE once.
+    Read,
+    /// Read repeatedly, `count` times or forever.
+    Watch {
+        /// Pause between samples.
+        interval: Duration,
+        /// Number of samples, unlimited if `None`.
+        count: Option<u64>,
+    },
+    /// Print magnet status, AGC, magnitude and burn count.
+    Status,
+    /// Print the CONF fields.
+    ConfigGet,
+    /// Change CONF fields.
+    ConfigSet(Vec<Setting>),
+    /// Set ZPOS to the current raw angle.
+    Zero,
+    /// Set MPOS and/or MANG.
+    Range {
+        /// New MPOS.
+        mpos: Option<Position>,
+        /// New MANG.
+        mang: Option<u16>,
+    },
+    /// Print all registers.
+    Dump,
+    /// Burn to OTP.
+    Burn {
+        /// What to burn.
+        target: BurnTarget,
+        /// Only check the preconditions.
+        dry_run: bool,
+        /// Skip the confirmation prompt.
+        yes: bool,
+    },
+}
+
+/// A position given on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    /// The current raw angle.
+    Here,
+    /// A fixed value in counts.
+    Counts(u16),
+}
+
+/// OTP burn selected by `burn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurnTarget {
+    /// ZPOS and MPOS (`BURN_ANGLE`, up to 3 times).
+    Angle,
+    /// MANG and CONF (`BURN_SETTING`, once).
+    Settings,
+}
+
+/// One `KEY=VALUE` of `config set`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    /// `power-mode`
+    PowerMode(PowerMode),
+    /// `hysteresis`
+    Hysteresis(Hysteresis),
+    /// `output`
+    OutputStage(OutputStage),
+    /// `pwm-freq`
+    PwmFrequency(PwmFrequency),
+    /// `slow-filter`
+    SlowFilter(SlowFilter),
+    /// `fast-filter`
+    FastFilterThreshold(FastFilterThreshold),
+    /// `watchdog`
+    Watchdog(bool),
+}
+
+impl Setting {
+    /// Applies the setting to `config`.
+    pub fn apply(self, config: &mut Configuration) {
+        match self {
+            Setting::PowerMode(v) => config.power_mode = v,
+            Setting::Hysteresis(v) => config.hysteresis = v,
+            Setting::OutputStage(v) => config.output_stage = v,
+            Setting::PwmFrequency(v) => config.pwm_frequency = v,
+            Setting::SlowFilter(v) => config.slow_filter = v,
+            Setting::FastFilterThreshold(v) => config.fast_filter_threshold = v,
+            Setting::Watchdog(v) => config.watchdog = v,
+        }
+    }
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// I2C bus device path.
+    pub bus: String,
+    /// Sensor address.
+    pub address: u8,
+    /// Print JSON instead of text.
+    pub json: bool,
+    /// Use the emulator instead of a bus.
+    pub mock: bool,
+    /// The sensor is an AS5600L.
+    pub as5600l: bool,
+    /// The subcommand.
+    pub command: Command,
+}
+
+/// Parses the command line (without the program name).
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+    let mut options = Options {
+        bus: DEFAULT_BUS.into(),
+        address: DEFAULT_ADDR,
+        json: false,
+        mock: false,
+        as5600l: false,
+        command: Command::Help,
+    };
+    let mut help = false;
+    let mut address = None;
+    let mut interval = Duration::from_millis(100);
+    let mut count = None;
+    let mut mpos = None;
+    let mut mang = None;
+    let mut dry_run = false;
+    let mut yes = false;
+    let mut words = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "--json" => options.json = true,
+            "--mock" if cfg!(feature = "mock") => options.mock = true,
+            "--mock" => {
+                return Err(CliError::Usage(
+                    "--mock needs a build with the cli-mock feature".into(),
+                ));
+            }
+            "--as5600l" => options.as5600l = true,
+            "--dry-run" => dry_run = true,
+            "-y" | "--yes" => yes = true,
+            "--bus" => options.bus = value(&mut args, "--bus")?,
+            "--address" => {
+                let addr = number(&value(&mut args, "--address")?, "--address", 0x7F)?;
+                address = Some(addr as u8);
+            }
+            "--interval" => {
+                let ms = number(&value(&mut args, "--interval")?, "--interval", u32::MAX)?;
+                interval = Duration::from_millis(ms as u64);
+            }
+            "--count" => {
+                count = Some(number(&value(&mut args, "--count")?, "--count", u32::MAX)? as u64);
+            }
+            "--mpos" => {
+                let v = value(&mut args, "--mpos")?;
+                mpos = Some(match v.as_str() {
+                    "here" => Position::Here,
+                    _ => Position::Counts(number(&v, "--mpos", 0x0FFF)? as u16),
+                });
+            }
+            "--mang" => {
+                mang = Some(number(&value(&mut args, "--mang")?, "--mang", 0x0FFF)? as u16);
+            }
+            opt if opt.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option `{}`", opt)));
+            }
+            _ => words.push(arg),
+        }
+    }
+    let default_addr = if options.as5600l {
+        AS5600L_DEFAULT_ADDR
+    } else {
+        DEFAULT_ADDR
+    };
+    options.address = address.unwrap_or(default_addr);
+    if help {
+        return Ok(options);
+    }
+
+    let mut words = words.into_iter();
+    let command = match words.next().as_deref() {
+        None => return Err(CliError::Usage("missing command".into())),
+        Some("read") => Command::Read,
+        Some("watch") => Command::Watch { interval, count },
+        Some("status") => Command::Status,
+        Some("config") => match words.next().as_deref() {
+            Some("get") => Command::ConfigGet,
+            Some("set") => {
+                let settings = words
+                    .by_ref()
+                    .map(|w| parse_setting(&w))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if settings.is_empty() {
+                    return Err(CliError::Usage(
+                        "`config set` needs KEY=VALUE arguments".into(),
+                    ));
+                }
+                Command::ConfigSet(settings)
+            }
+            _ => {
+                return Err(CliError::Usage(
+                    "expected `config get` or `config set`".into(),
+                ));
+            }
+        },
+        Some("zero") => Command::Zero,
+        Some("range") => {
+            if mpos.is_none() && mang.is_none() {
+                return Err(CliError::Usage("`range` needs --mpos and/or --mang".into()));
+            }
+            Command::Range { mpos, mang }
+        }
+        Some("dump") => Command::Dump,
+        Some("burn") => {
+            let target = match words.next().as_deref() {
+                Some("angle") => BurnTarget::Angle,
+                Some("settings") => BurnTarget::Settings,
+                _ => {
+                    return Err(CliError::Usage(
+                        "expected `burn angle` or `burn settings`".into(),
+                    ));
+                }
+            };
+            Command::Burn {
+                target,
+                dry_run,
+                yes,
+            }
+        }
+        Some(other) => return Err(CliError::Usage(format!("unknown command `{}`", other))),
+    };
+    if let Some(extra) = words.next() {
+        return Err(CliError::Usage(format!("unexpected argument `{}`", extra)));
+    }
+    options.command = command;
+    Ok(options)
+}
+
+/// Takes the value of option `name`.
+fn value(args: &mut impl Iterator<Item = String>, name: &str) -> Result<String, CliError> {
+    args.next()
+        .ok_or_else(|| CliError::Usage(format!("{} needs a value", name)))
+}
+
+/// Parses a decimal or `0x` hexadecimal number of at most `max`.
+fn number(text: &str, name: &str, max: u32) -> Result<u32, CliError> {
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    match parsed {
+        Ok(v) if v <= max => Ok(v),
+        _ => Err(CliError::Usage(format!(
+            "invalid value `{}` for {}",
+            text, name
+        ))),
+    }
+}
+
+/// Parses one `KEY=VALUE` of `config set`.
+fn parse_setting(text: &str) -> Result<Setting, CliError> {
+    let invalid = || CliError::Usage(format!("invalid setting `{}`", text));
+    let (key, val) = text.split_once('=').ok_or_else(invalid)?;
+    let setting = match key {
+        "power-mode" => Setting::PowerMode(match val {
+            "nom" => PowerMode::Nominal,
+            "lpm1" => PowerMode::LPM1,
+            "lpm2" => PowerMode::LPM2,
+            "lpm3" => PowerMode::LPM3,
+            _ => return Err(invalid()),
+        }),
+        "hysteresis" => Setting::Hysteresis(match val {
+            "off" => Hysteresis::Off,
+            "1" => Hysteresis::Lsb1,
+            "2" => Hysteresis::Lsb2,
+            "3" => Hysteresis::Lsb3,
+            _ => return Err(invalid()),
+        }),
+        "output" => Setting::OutputStage(match val {
+            "analog" => OutputStage::AnalogFull,
+            "analog-reduced" => OutputStage::AnalogReduced,
+            "pwm" => OutputStage::PWM,
+            _ => return Err(invalid()),
+        }),
+        "pwm-freq" => Setting::PwmFrequency(match val {
+            "115" => PwmFrequency::Hz115,
+            "230" => PwmFrequency::Hz230,
+            "460" => PwmFrequency::Hz460,
+            "920" => PwmFrequency::Hz920,
+            _ => return Err(invalid()),
+        }),
+        "slow-filter" => Setting::SlowFilter(match val {
+            "16x" => SlowFilter::X16,
+            "8x" => SlowFilter::X8,
+            "4x" => SlowFilter::X4,
+            "2x" => SlowFilter::X2,
+            _ => return Err(invalid()),
+        }),
+        "fast-filter" => Setting::FastFilterThreshold(match val {
+            "off" => FastFilterThreshold::SlowOnly,
+            "6" => FastFilterThreshold::Lsb6,
+            "7" => FastFilterThreshold::Lsb7,
+            "9" => FastFilterThreshold::Lsb9,
+            "10" => FastFilterThreshold::Lsb10,
+            "18" => FastFilterThreshold::Lsb18,
+            "21" => FastFilterThreshold::Lsb21,
+            "24" => FastFilterThreshold::Lsb24,
+            _ => return Err(invalid()),
+        }),
+        "watchdog" => Setting::Watchdog(match val {
+            "on" => true,
+            "off" => false,
+            _ => return Err(invalid()),
+        }),
+        _ => return Err(invalid()),
+    };
+    Ok(setting)
+}
+
+/// A value printed by the tool.
+enum Value {
+    Int(i64),
+    Float(f32),
+    Bool(bool),
+    Str(&'static str),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{:.2}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Str(v) if f.alternate() => write!(f, "\"{}\"", v),
+            Value::Str(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Prints `fields` as one JSON object, `key: value` lines, or a single
+/// `key=value` line (`inline`).
+fn emit(
+    out: &mut impl Write,
+    json: bool,
+    inline: bool,
+    fields: &[(&str, Value)],
+) -> io::Result<()> {
+    if json {
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\":{:#}", key, value))
+            .collect();
+        return writeln!(out, "{{{}}}", body.join(","));
+    }
+    if inline {
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        return writeln!(out, "{}", body.join(" "));
+    }
+    for (key, value) in fields {
+        writeln!(out, "{:<14} {}", key, value)?;
+    }
+    Ok(())
+}
+
+/// Sleeps with `std::thread::sleep`, for the OTP burn wait.
+struct StdDelay;
+
+impl DelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns as u64));
+    }
+}
+
+/// Runs `options.command` against the sensor on `i2c`.
+///
+/// `input` answers the burn confirmation, which is printed to `prompt` (stderr in
+/// the binary, so `--json` output stays parseable); everything else goes to `out`.
+pub fn run<I2C>(
+    mut i2c: I2C,
+    options: &Options,
+    input: &mut impl BufRead,
+    prompt: &mut impl Write,
+    out: &mut impl Write,
+) -> Result<(), CliError>
+where
+    I2C: I2c<SevenBitAddress>,
+    I2C::Error: fmt::Debug,
+{
+    let json = options.json;
+    if options.command == Command::Dump {
+        return dump(&mut i2c, options.address, options.as5600l, json, out);
+    }
+    let mut sensor = AS5600Driver::with_address(i2c, options.address);
+
+    match &options.command {
+        Command::Help => writeln!(out, "{}", USAGE)?,
+        Command::Read => emit(out, json, false, &angle_fields(&mut sensor)?)?,
+        Command::Watch { interval, count } => {
+            let mut taken = 0;
+            while count.is_none_or(|n| taken < n) {
+                if taken > 0 {
+                    thread::sleep(*interval);
+                }
+                emit(out, json, true, &angle_fields(&mut sensor)?)?;
+                out.flush()?;
+                taken += 1;
+            }
+        }
+        Command::Status => {
+            let status = sensor.get_magnet_status()?;
+            let fields = [
+                ("detected", Value::Bool(status.detected)),
+                ("too_weak", Value::Bool(status.too_weak)),
+                ("too_strong", Value::Bool(status.too_strong)),
+                ("agc", Value::Int(sensor.get_agc()? as i64)),
+                ("magnitude", Value::Int(sensor.get_magnitude()? as i64)),
+                ("burn_count", Value::Int(sensor.get_burn_count()? as i64)),
+            ];
+            emit(out, json, false, &fields)?;
+        }
+        Command::ConfigGet => emit(out, json, false, &config_fields(&sensor.get_config()?))?,
+        Command::ConfigSet(settings) => {
+            sensor.modify_config(|config| {
+                for setting in settings {
+                    setting.apply(config);
+                }
+            })?;
+            emit(out, json, false, &config_fields(&sensor.get_config()?))?;
+        }
+        Command::Zero => {
+            let raw = sensor.read_raw_angle()?;
+            sensor.set_zero_position(raw)?;
+            emit(out, json, false, &range_fields(&mut sensor)?)?;
+        }
+        Command::Range { mpos, mang } => {
+            if let Some(mpos) = mpos {
+                let value = match mpos {
+                    Position::Here => sensor.read_raw_angle()?,
+                    Position::Counts(counts) => *counts,
+                };
+                sensor.set_max_position(value)?;
+            }
+            if let Some(mang) = mang {
+                sensor.set_max_angle(*mang)?;
+            }
+            emit(out, json, false, &range_fields(&mut sensor)?)?;
+        }
+        Command::Dump => unreachable!("handled before the driver takes the bus"),
+        Command::Burn {
+            target,
+            dry_run,
+            yes,
+        } => burn(&mut sensor, *target, *dry_run, json, out, |fields| {
+            if *yes {
+                return Ok(());
+            }
+            confirm_burn(fields, input, prompt)
+        })?,
+    }
+    Ok(())
+}
+
+fn angle_fields<I: AS5600Interface>(
+    sensor: &mut I,
+) -> Result<[(&'static str, Value); 4], AS56Error<I::Error>> {
+    let raw = sensor.read_raw_angle()?;
+    let angle = sensor.read_angle()?;
+    Ok([
+        ("raw_angle", Value::Int(raw as i64)),
+        (
+            "raw_degrees",
+            Value::Float(RawCounts::from_masked(raw).to_degrees()),
+        ),
+        ("angle", Value::Int(angle as i64)),
+        (
+            "degrees",
+            Value::Float(Angle::from_masked(angle).to_degrees()),
+        ),
+    ])
+}
+
+fn range_fields<I: AS5600Interface>(
+    sensor: &mut I,
+) -> Result<[(&'static str, Value); 3], AS56Error<I::Error>> {
+    Ok([
+        ("zpos", Value::Int(sensor.get_zero_position()? as i64)),
+        ("mpos", Value::Int(sensor.get_max_position()? as i64)),
+        ("mang", Value::Int(sensor.get_max_angle()? as i64)),
+    ])
+}
+
+fn config_fields(config: &Configuration) -> [(&'static str, Value); 7] {
+    [
+        (
+            "power-mode",
+            Value::Str(match config.power_mode {
+                PowerMode::Nominal => "nom",
+                PowerMode::LPM1 => "lpm1",
+                PowerMode::LPM2 => "lpm2",
+                PowerMode::LPM3 => "lpm3",
+            }),
+        ),
+        (
+            "hysteresis",
+            Value::Str(match config.hysteresis {
+                Hysteresis::Off => "off",
+                Hysteresis::Lsb1 => "1",
+                Hysteresis::Lsb2 => "2",
+                Hysteresis::Lsb3 => "3",
+            }),
+        ),
+        (
+            "output",
+            Value::Str(match config.output_stage {
+                OutputStage::AnalogFull => "analog",
+                OutputStage::AnalogReduced => "analog-reduced",
+                OutputStage::PWM => "pwm",
+            }),
+        ),
+        (
+            "pwm-freq",
+            Value::Str(match config.pwm_frequency {
+                PwmFrequency::Hz115 => "115",
+                PwmFrequency::Hz230 => "230",
+                PwmFrequency::Hz460 => "460",
+                PwmFrequency::Hz920 => "920",
+            }),
+        ),
+        (
+            "slow-filter",
+            Value::Str(match config.slow_filter {
+                SlowFilter::X16 => "16x",
+                SlowFilter::X8 => "8x",
+                SlowFilter::X4 => "4x",
+                SlowFilter::X2 => "2x",
+            }),
+        ),
+        (
+            "fast-filter",
+            Value::Str(match config.fast_filter_threshold {
+                FastFilterThreshold::SlowOnly => "off",
+                FastFilterThreshold::Lsb6 => "6",
+                FastFilterThreshold::Lsb7 => "7",
+                FastFilterThreshold::Lsb9 => "9",
+                FastFilterThreshold::Lsb10 => "10",
+                FastFilterThreshold::Lsb18 => "18",
+                FastFilterThreshold::Lsb21 => "21",
+                FastFilterThreshold::Lsb24 => "24",
+            }),
+        ),
+        (
+            "watchdog",
+            Value::Str(if config.watchdog { "on" } else { "off" }),
+        ),
+    ]
+}
+
+/// Prints the programming, output and BURN registers, plus the address registers
+/// of an AS5600L.
+fn dump<I2C>(
+    i2c: &mut I2C,
+    address: u8,
+    as5600l: bool,
+    json: bool,
+    out: &mut impl Write,
+) -> Result<(), CliError>
+where
+    I2C: I2c<SevenBitAddress>,
+    I2C::Error: fmt::Debug,
+{
+    let mut registers = Vec::new();
+    let mut read = |start: u8, len: usize| -> Result<(), CliError> {
+        let mut buf = [0u8; 16];
+        i2c.write_read(address, &[start], &mut buf[..len])
+            .map_err(AS56Error::bus(address))?;
+        registers.extend((start..=u8::MAX).zip(buf[..len].iter().copied()));
+        Ok(())
+    };
+    read(regs::ZMCO, 16)?;
+    read(regs::AGC, 3)?;
+    if as5600l {
+        read(regs::I2C_ADDR, 2)?;
+    }
+    read(regs::BURN, 1)?;
+
+    // Only named registers are dumped.
+    let name = |reg| register_name(reg).unwrap_or_default();
+    if json {
+        let body: Vec<String> = registers
+            .iter()
+            .map(|&(reg, val)| format!("\"{}\":\"0x{:02X}\"", name(reg), val))
+            .collect();
+        writeln!(out, "{{{}}}", body.join(","))?;
+    } else {
+        for (reg, val) in registers {
+            writeln!(out, "0x{:02X}  {:<12}  0x{:02X}", reg, name(reg), val)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the preconditions of a burn, asks `confirm` and burns.
+fn burn<I2C>(
+    sensor: &mut AS5600Driver<I2C>,
+    target: BurnTarget,
+    dry_run: bool,
+    json: bool,
+    out: &mut impl Write,
+    confirm: impl FnOnce(&[(&'static str, Value)]) -> Result<(), CliError>,
+) -> Result<(), CliError>
+where
+    I2C: I2c<SevenBitAddress>,
+    I2C::Error: fmt::Debug,
+{
+    let zmco = sensor.get_burn_count()?;
+    let (mut fields, check) = match target {
+        BurnTarget::Angle => {
+            let status = sensor.get_status_raw()?;
+            let zpos = sensor.get_zero_position()?;
+            let mpos = sensor.get_max_position()?;
+            let fields = Vec::from([
+                ("zpos", Value::Int(zpos as i64)),
+                ("mpos", Value::Int(mpos as i64)),
+                ("burns_left", Value::Int(3 - zmco.min(3) as i64)),
+            ]);
+            (
+                fields,
+                check_angle_burn::<I2C::Error>(status, zmco, zpos, mpos),
+            )
+        }
+        BurnTarget::Settings => {
+            let mang = sensor.get_max_angle()?;
+            let fields = Vec::from([
+                ("mang", Value::Int(mang as i64)),
+                ("burns_left", Value::Int(if zmco == 0 { 1 } else { 0 })),
+            ]);
+            (fields, check_setting_burn::<I2C::Error>(zmco))
+        }
+    };
+
+    if let Err(e) = check {
+        return Err(e.into());
+    }
+    if dry_run {
+        fields.push(("dry_run", Value::Bool(true)));
+        return Ok(emit(out, json, false, &fields)?);
+    }
+    confirm(&fields)?;
+
+    // SAFETY: the user confirmed the irreversible write (or passed --yes).
+    unsafe {
+        match target {
+            BurnTarget::Angle => sensor.danger_verified_burn_settings(&mut StdDelay)?,
+            BurnTarget::Settings => sensor.danger_verified_burn_config(&mut StdDelay)?,
+        }
+    }
+    fields.retain(|(key, _)| *key != "burns_left");
+    fields.push(("burned", Value::Bool(true)));
+    emit(out, json, false, &fields)?;
+    Ok(())
+}
+
+/// Shows what a burn will program on `prompt` and waits for `BURN` on `input`.
+fn confirm_burn(
+    fields: &[(&'static str, Value)],
+    input: &mut impl BufRead,
+    prompt: &mut impl Write,
+) -> Result<(), CliError> {
+    emit(prompt, false, false, fields)?;
+    write!(
+        prompt,
+        "This permanently programs the sensor's OTP memory. Type BURN to continue: "
+    )?;
+    prompt.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    if answer.trim() != "BURN" {
+        return Err(CliError::Aborted);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::AS56Mock;
+
+    /// Parses `args` and runs them against `mock`, answering prompts with `input`.
+    ///
+    /// Returns what was printed to the output and to the prompt stream.
+    fn cli_streams(mock: &AS56Mock, args: &str, input: &str) -> Result<(String, String), CliError> {
+        let options = parse_args(args.split_whitespace().map(String::from))?;
+        let (mut prompt, mut out) = (Vec::new(), Vec::new());
+        run(
+            mock.clone(),
+            &options,
+            &mut input.as_bytes(),
+            &mut prompt,
+            &mut out,
+        )?;
+        Ok((
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(prompt).unwrap(),
+        ))
+    }
+
+    /// Like [`cli_streams`], returning only the output.
+    fn cli(mock: &AS56Mock, args: &str, input: &str) -> Result<String, CliError> {
+        cli_streams(mock, args, input).map(|(out, _)| out)
+    }
+
+    #[test]
+    fn parses_global_options() {
+        let options =
+            parse_args(["--json", "--address", "0x37", "read"].map(String::from)).unwrap();
+        assert_eq!(options.address, 0x37);
+        assert!(options.json);
+        assert_eq!(options.command, Command::Read);
+
+        let options = parse_args(["--as5600l", "dump"].map(String::from)).unwrap();
+        assert_eq!(options.address, AS5600L_DEFAULT_ADDR);
+
+        let err = parse_args(["config", "set", "output=hdmi"].map(String::from)).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn read_prints_both_angles() {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(1024);
+        let text = cli(&mock, "read", "").unwrap();
+        assert!(text.contains("raw_angle      1024\n"), "{text}");
+        assert!(text.contains("raw_degrees    90.00\n"), "{text}");
+        assert!(text.contains("degrees        90.00\n"), "{text}");
+
+        let json = cli(&mock, "--json read", "").unwrap();
+        assert_eq!(
+            json,
+            "{\"raw_angle\":1024,\"raw_degrees\":90.00,\"angle\":1024,\"degrees\":90.00}\n"
+        );
+    }
+
+    #[test]
+    fn config_set_changes_only_the_given_fields() {
+        let mock = AS56Mock::new();
+        let text = cli(&mock, "config set power-mode=lpm2 slow-filter=4x", "").unwrap();
+        assert!(text.contains("power-mode     lpm2\n"), "{text}");
+        assert!(text.contains("slow-filter    4x\n"), "{text}");
+        assert!(text.contains("watchdog       on\n"), "{text}");
+        mock.mock_assert_write(regs::CONF_HI, &[0x22, 0x02]);
+
+        let json = cli(&mock, "--json config set hysteresis=3", "").unwrap();
+        assert!(
+            json.starts_with("{\"power-mode\":\"lpm2\",\"hysteresis\":\"3\","),
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn zero_and_range_write_the_programming_registers() {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(300);
+        let text = cli(&mock, "zero", "").unwrap();
+        assert_eq!(
+            text,
+            "zpos           300\nmpos           0\nmang           0\n"
+        );
+
+        mock.mock_set_raw_angle(3000);
+        let json = cli(&mock, "--json range --mpos here --mang 2048", "").unwrap();
+        assert_eq!(json, "{\"zpos\":300,\"mpos\":3000,\"mang\":2048}\n");
+    }
+
+    #[test]
+    fn dump_prints_every_register_as_hex() {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(0x0ABC);
+        let text = cli(&mock, "dump", "").unwrap();
+        assert!(text.contains("0x0C  RAW_ANGLE_HI  0x0A\n"), "{text}");
+        assert!(text.contains("0xFF  BURN          0x00\n"), "{text}");
+        assert!(!text.contains("I2C_ADDR"), "{text}");
+
+        let json = cli(&mock, "--json dump", "").unwrap();
+        assert!(json.starts_with("{\"ZMCO\":\"0x00\","), "{json}");
+        assert!(json.contains("\"CONF_HI\":\"0x20\""), "{json}");
+        assert!(json.contains("\"RAW_ANGLE_LO\":\"0xBC\""), "{json}");
+        assert!(json.contains("\"AGC\":\"0x64\""), "{json}");
+        assert!(json.ends_with("\"BURN\":\"0x00\"}\n"), "{json}");
+        assert!(!json.contains("I2C_ADDR"), "{json}");
+
+        let mock = AS56Mock::new_as5600l();
+        let json = cli(&mock, "--as5600l --json dump", "").unwrap();
+        assert!(
+            json.contains("\"I2C_ADDR\":\"0x80\",\"I2C_UPDT\":\"0x80\""),
+            "{json}"
+        );
+        assert_eq!(json.matches(":\"0x").count(), 16 + 3 + 2 + 1);
+    }
+
+    #[test]
+    fn burn_dry_run_checks_without_burning() {
+        let mock = AS56Mock::new();
+        cli(&mock, "range --mpos 2048", "").unwrap();
+        mock.mock_set_raw_angle(100);
+        cli(&mock, "zero", "").unwrap();
+
+        let text = cli(&mock, "burn angle --dry-run", "").unwrap();
+        assert!(text.contains("burns_left     3\n"), "{text}");
+        assert!(text.contains("dry_run        true\n"), "{text}");
+        let json = cli(&mock, "--json burn settings --dry-run", "").unwrap();
+        assert_eq!(json, "{\"mang\":0,\"burns_left\":1,\"dry_run\":true}\n");
+        assert_eq!(mock.mock_otp().zmco, 0);
+        mock.mock_assert_no_write(regs::BURN);
+    }
+
+    #[test]
+    fn burn_asks_for_confirmation() {
+        let mock = AS56Mock::new();
+        cli(&mock, "range --mpos 2048", "").unwrap();
+        mock.mock_set_raw_angle(100);
+        cli(&mock, "zero", "").unwrap();
+
+        let err = cli(&mock, "burn angle", "yes\n").unwrap_err();
+        assert!(matches!(err, CliError::Aborted));
+        mock.mock_assert_no_write(regs::BURN);
+
+        let (text, prompt) = cli_streams(&mock, "burn angle", "BURN\n").unwrap();
+        assert!(prompt.ends_with("Type BURN to continue: "), "{prompt}");
+        assert!(!text.contains("Type BURN"), "{text}");
+        assert!(text.ends_with("burned         true\n"), "{text}");
+        assert_eq!((mock.mock_otp().zpos, mock.mock_otp().mpos), (100, 2048));
+
+        // With --json the output is only the result, the prompt goes to its own stream.
+        let mock = AS56Mock::new();
+        cli(&mock, "range --mpos 2048", "").unwrap();
+        mock.mock_set_raw_angle(100);
+        cli(&mock, "zero", "").unwrap();
+        let (json, prompt) = cli_streams(&mock, "--json burn angle", "BURN\n").unwrap();
+        assert_eq!(json, "{\"zpos\":100,\"mpos\":2048,\"burned\":true}\n");
+        assert!(prompt.contains("zpos           100\n"), "{prompt}");
+        assert!(prompt.ends_with("Type BURN to continue: "), "{prompt}");
+
+        // The settings burn is only allowed before any angle burn.
+        let mock = AS56Mock::new();
+        let json = cli(&mock, "--json burn settings --yes", "").unwrap();
+        assert_eq!(json, "{\"mang\":0,\"burned\":true}\n");
+        assert!(mock.mock_otp().settings_burned);
+    }
+
+    #[test]
+    fn burn_refuses_failed_preconditions() {
+        let mock = AS56Mock::new();
+        let err = cli(&mock, "burn angle --yes", "").unwrap_err();
+        assert!(matches!(err, CliError::Sensor(_)), "{err}");
+        mock.mock_assert_no_write(regs::BURN);
+    }
+}
@@ -0,0 +1,688 @@
+//! Terminal dashboard showing every register of a sensor.
+//!
+//! [`Dashboard`] renders any [`AS5600Interface`] into a [`core::fmt::Write`], so it
+//! works on `no_std` targets without an allocator (e.g. through a UART printer) as
+//! well as on a desktop terminal. With the `std` feature, [`Dashboard::live`]
+//! redraws the dashboard in place at a fixed interval.
+
+use crate::error::AS56Error;
+use crate::traits::AS5600Interface;
+use crate::types::*;
+use core::fmt::{self, Write};
+
+/// Narrowest supported dashboard, in terminal columns.
+pub const MIN_WIDTH: usize = 48;
+/// Widest supported dashboard, in terminal columns.
+pub const MAX_WIDTH: usize = 120;
+
+/// Narrowest column that fits a `label value` pair.
+const PAIR_COLUMN: usize = 30;
+
+/// Character set used to draw the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Box-drawing characters, emoji and smooth sub-character progress bars.
+    Emoji,
+    /// Plain 7-bit ASCII, for serial consoles and log files.
+    Ascii,
+}
+
+/// Sections shown on the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sections {
+    /// Raw and scaled angle with progress bars.
+    pub position: bool,
+    /// Magnet status, magnitude and AGC.
+    pub magnet: bool,
+    /// CONF register fields.
+    pub config: bool,
+    /// Burn count and ZPOS/MPOS/MANG.
+    pub memory: bool,
+}
+
+impl Sections {
+    /// Every section.
+    pub const ALL: Self = Self {
+        position: true,
+        magnet: true,
+        config: true,
+        memory: true,
+    };
+    /// Only the position section, for a compact live view.
+    pub const POSITION: Self = Self {
+        position: true,
+        magnet: false,
+        config: false,
+        memory: false,
+    };
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Error returned when rendering a dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderError<E> {
+    /// Reading the sensor failed; nothing was written.
+    Sensor(AS56Error<E>),
+    /// The output rejected the text.
+    Format,
+}
+
+impl<E> From<AS56Error<E>> for RenderError<E> {
+    fn from(e: AS56Error<E>) -> Self {
+        RenderError::Sensor(e)
+    }
+}
+
+impl<E> From<fmt::Error> for RenderError<E> {
+    fn from(_: fmt::Error) -> Self {
+        RenderError::Format
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for RenderError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Sensor(e) => write!(f, "{}", e),
+            RenderError::Format => write!(f, "formatting error"),
+        }
+    }
+}
+
+/// Register values shown on one frame, read before anything is drawn.
+///
+/// Only the registers of the enabled sections are read; the others stay `None`.
+struct Readings {
+    live: Option<Snapshot>,
+    config: Option<Configuration>,
+    memory: Option<Memory>,
+}
+
+/// Registers of the memory section.
+struct Memory {
+    burn_count: u8,
+    zpos: u16,
+    mpos: u16,
+    mang: u16,
+}
+
+/// Renders a full-register dashboard of a sensor.
+///
+/// ```rust,ignore
+/// use AS5600_Driver::dashboard::{Dashboard, Style};
+///
+/// let dashboard = Dashboard::new().with_width(72).with_style(Style::Ascii);
+/// dashboard.render(&mut encoder, &mut uart)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dashboard {
+    width: usize,
+    style: Style,
+    sections: Sections,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dashboard {
+    /// Creates a 64 column emoji dashboard with every section.
+    pub const fn new() -> Self {
+        Self {
+            width: 64,
+            style: Style::Emoji,
+            sections: Sections::ALL,
+        }
+    }
+
+    /// Sets the total width in terminal columns, clamped to
+    /// [`MIN_WIDTH`]..=[`MAX_WIDTH`].
+    pub const fn with_width(mut self, width: usize) -> Self {
+        self.width = if width < MIN_WIDTH {
+            MIN_WIDTH
+        } else if width > MAX_WIDTH {
+            MAX_WIDTH
+        } else {
+            width
+        };
+        self
+    }
+
+    /// Sets the character set.
+    pub const fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Selects the sections to show.
+    pub const fn with_sections(mut self, sections: Sections) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    /// Reads the sensor and writes one frame to `out`.
+    ///
+    /// Only the registers of the enabled sections are read, all of them before
+    /// drawing, so a failing sensor leaves `out` untouched. The position and
+    /// magnet values come from one [`Snapshot`], so they belong to the same sample.
+    pub fn render<S, W>(&self, sensor: &mut S, out: &mut W) -> Result<(), RenderError<S::Error>>
+    where
+        S: AS5600Interface,
+        W: Write,
+    {
+        let sections = self.sections;
+        let live = if sections.position || sections.magnet {
+            Some(sensor.read_snapshot()?)
+        } else {
+            None
+        };
+        let config = if sections.config {
+            Some(sensor.get_config()?)
+        } else {
+            None
+        };
+        let memory = if sections.memory {
+            Some(Memory {
+                burn_count: sensor.get_burn_count()?,
+                zpos: sensor.get_zero_position()?,
+                mpos: sensor.get_max_position()?,
+                mang: sensor.get_max_angle()?,
+            })
+        } else {
+            None
+        };
+        let readings = Readings {
+            live,
+            config,
+            memory,
+        };
+        self.draw(&readings, out)?;
+        Ok(())
+    }
+
+    /// Writes the frame shown when the sensor does not respond.
+    pub fn render_disconnected<W: Write>(&self, out: &mut W) -> fmt::Result {
+        self.border(out, Border::Top)?;
+        let title = match self.style {
+            Style::Emoji => "⚠️  AS5600 DISCONNECTED!  ⚠️",
+            Style::Ascii => "!! AS5600 DISCONNECTED !!",
+        };
+        self.centered(out, title)?;
+        self.border(out, Border::Bottom)
+    }
+
+    fn draw<W: Write>(&self, r: &Readings, out: &mut W) -> fmt::Result {
+        let emoji = self.style == Style::Emoji;
+        self.border(out, Border::Top)?;
+        let title = if emoji {
+            "🛰️  AS5600 FULL REGISTER MONITOR"
+        } else {
+            "AS5600 FULL REGISTER MONITOR"
+        };
+        self.centered(out, title)?;
+
+        if let (true, Some(live)) = (self.sections.position, &r.live) {
+            self.border(out, Border::Separator)?;
+            self.heading(out, "📍", "POSITION DATA")?;
+            self.position(out, "Raw Angle:", live.raw_angle)?;
+            self.position(out, "Filtered: ", live.angle)?;
+        }
+
+        if let (true, Some(live)) = (self.sections.magnet, &r.live) {
+            self.border(out, Border::Separator)?;
+            self.heading(out, "🧲", "MAGNET DIAGNOSTICS")?;
+            let (yes, no, ok, low, high) = if emoji {
+                ("✅ YES", "❌ NO", "✅ OK", "⚠️ LOW", "⚠️ HIGH")
+            } else {
+                ("YES", "NO", "OK", "LOW", "HIGH")
+            };
+            let detected = if live.status.detected { yes } else { no };
+            let weak = if live.status.too_weak { low } else { ok };
+            let strong = if live.status.too_strong { high } else { ok };
+            self.pair(
+                out,
+                ("Detected:", format_args!("{}", detected)),
+                (
+                    "Field Status:",
+                    format_args!("0x{:02X} Raw", live.status_raw),
+                ),
+            )?;
+            self.pair(
+                out,
+                ("Too Weak:", format_args!("{}", weak)),
+                ("Too Strong:", format_args!("{}", strong)),
+            )?;
+            self.pair(
+                out,
+                ("Magnitude:", format_args!("{}", live.magnitude)),
+                ("AGC Value:", format_args!("{}", live.agc)),
+            )?;
+        }
+
+        if let Some(c) = &r.config {
+            self.border(out, Border::Separator)?;
+            self.heading(out, "⚙️", "CHIP CONFIGURATION (CONF)")?;
+            let watchdog = match (c.watchdog, emoji) {
+                (true, true) => "⚡ ON",
+                (false, true) => "💤 OFF",
+                (true, false) => "ON",
+                (false, false) => "OFF",
+            };
+            self.pair(
+                out,
+                ("Watchdog:", format_args!("{}", watchdog)),
+                ("Power Mode:", format_args!("{:?}", c.power_mode)),
+            )?;
+            self.pair(
+                out,
+                ("Hysteresis:", format_args!("{:?}", c.hysteresis)),
+                ("Output Stage:", format_args!("{:?}", c.output_stage)),
+            )?;
+            self.pair(
+                out,
+                ("PWM Frequency:", format_args!("{:?}", c.pwm_frequency)),
+                ("Slow Filter:", format_args!("{:?}", c.slow_filter)),
+            )?;
+            self.line(
+                out,
+                format_args!("   {:<17}{:?}", "Fast Threshold:", c.fast_filter_threshold),
+            )?;
+        }
+
+        if let Some(m) = &r.memory {
+            self.border(out, Border::Separator)?;
+            self.heading(out, "💾", "MEMORY & OPERATING RANGES")?;
+            self.pair(
+                out,
+                ("Burn Cycles:", format_args!("{}/3", m.burn_count)),
+                ("Zero Pos (ZPOS):", format_args!("{}", m.zpos)),
+            )?;
+            self.pair(
+                out,
+                ("Max Pos (MPOS):", format_args!("{}", m.mpos)),
+                ("Max Ang (MANG):", format_args!("{}", m.mang)),
+            )?;
+        }
+
+        self.border(out, Border::Bottom)
+    }
+
+    /// Number of columns between the two vertical borders and their padding.
+    fn inner(&self) -> usize {
+        self.width - 4
+    }
+
+    fn border<W: Write>(&self, out: &mut W, border: Border) -> fmt::Result {
+        let (left, fill, right) = match (self.style, border) {
+            (Style::Emoji, Border::Top) => ('╔', '═', '╗'),
+            (Style::Emoji, Border::Separator) => ('╠', '═', '╣'),
+            (Style::Emoji, Border::Bottom) => ('╚', '═', '╝'),
+            (Style::Ascii, _) => ('+', '-', '+'),
+        };
+        out.write_char(left)?;
+        repeat(out, fill, self.width - 2)?;
+        out.write_char(right)?;
+        out.write_char('\n')
+    }
+
+    /// Writes one row, padding `content` to the inner width.
+    fn line<W: Write>(&self, out: &mut W, content: fmt::Arguments<'_>) -> fmt::Result {
+        let vertical = match self.style {
+            Style::Emoji => '║',
+            Style::Ascii => '|',
+        };
+        let mut counter = WidthCounter(0);
+        counter.write_fmt(content)?;
+        write!(out, "{} ", vertical)?;
+        out.write_fmt(content)?;
+        repeat(out, ' ', self.inner().saturating_sub(counter.0))?;
+        writeln!(out, " {}", vertical)
+    }
+
+    fn centered<W: Write>(&self, out: &mut W, text: &str) -> fmt::Result {
+        let pad = self.inner().saturating_sub(display_width(text)) / 2;
+        self.line(out, format_args!("{:pad$}{}", "", text, pad = pad))
+    }
+
+    fn heading<W: Write>(&self, out: &mut W, icon: &str, title: &str) -> fmt::Result {
+        match self.style {
+            Style::Emoji => self.line(out, format_args!("{} {}", icon, title)),
+            Style::Ascii => self.line(out, format_args!("{}", title)),
+        }
+    }
+
+    /// Writes two `label value` columns, or two rows if they do not fit side by side.
+    fn pair<W: Write>(
+        &self,
+        out: &mut W,
+        left: (&str, fmt::Arguments<'_>),
+        right: (&str, fmt::Arguments<'_>),
+    ) -> fmt::Result {
+        let column = self.inner() / 2;
+        if column < PAIR_COLUMN {
+            self.line(out, format_args!("   {:<17}{}", left.0, left.1))?;
+            return self.line(out, format_args!("   {:<17}{}", right.0, right.1));
+        }
+        let mut counter = WidthCounter(0);
+        write!(counter, "   {:<17}{}", left.0, left.1)?;
+        let pad = column.saturating_sub(counter.0).max(1);
+        self.line(
+            out,
+            format_args!(
+                "   {:<17}{}{:pad$}{:<17}{}",
+                left.0,
+                left.1,
+                "",
+                right.0,
+                right.1,
+                pad = pad
+            ),
+        )
+    }
+
+    /// Writes a value with its share of the full scale and a progress bar.
+    fn position<W: Write>(&self, out: &mut W, label: &str, value: u16) -> fmt::Result {
+        let percent = value as u32 * 100 / 4095;
+        let prefix_width = 3 + display_width(label) + 18;
+        let bar_width = self.inner().saturating_sub(prefix_width + 2).max(1);
+        let bar = Bar {
+            value,
+            width: bar_width,
+            style: self.style,
+        };
+        let (open, close) = match self.style {
+            Style::Emoji => ('│', '│'),
+            Style::Ascii => ('[', ']'),
+        };
+        self.line(
+            out,
+            format_args!(
+                "   {} {:>4} / 4095 {:>3}% {}{}{}",
+                label, value, percent, open, bar, close
+            ),
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Border {
+    Top,
+    Separator,
+    Bottom,
+}
+
+/// Progress bar of a 12-bit value.
+struct Bar {
+    value: u16,
+    width: usize,
+    style: Style,
+}
+
+impl fmt::Display for Bar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+        let steps = match self.style {
+            Style::Emoji => 8,
+            Style::Ascii => 1,
+        };
+        let filled = self.value as usize * self.width * steps / 4095;
+        let full = (filled / steps).min(self.width);
+        let (block, empty) = match self.style {
+            Style::Emoji => ('█', ' '),
+            Style::Ascii => ('#', '.'),
+        };
+        repeat(f, block, full)?;
+        if full < self.width {
+            let partial = match self.style {
+                Style::Emoji => EIGHTHS[filled % steps],
+                Style::Ascii => empty,
+            };
+            f.write_char(partial)?;
+            repeat(f, empty, self.width - full - 1)?;
+        }
+        Ok(())
+    }
+}
+
+fn repeat<W: Write + ?Sized>(out: &mut W, c: char, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        out.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Counts the terminal columns of formatted text without storing it.
+struct WidthCounter(usize);
+
+impl Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += display_width(s);
+        Ok(())
+    }
+}
+
+/// Approximates the number of terminal columns `text` occupies: emoji take two,
+/// variation selectors none, everything else one.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0xFE00..=0xFE0F | 0x200D => 0,
+            0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(feature = "std")]
+impl Dashboard {
+    /// Redraws the dashboard in place every `interval`, forever.
+    ///
+    /// Shows [`render_disconnected`](Self::render_disconnected) while the sensor
+    /// does not respond. Returns only if writing to `out` fails.
+    pub fn live<S, W>(
+        &self,
+        sensor: &mut S,
+        out: &mut W,
+        interval: core::time::Duration,
+    ) -> std::io::Result<()>
+    where
+        S: AS5600Interface,
+        W: std::io::Write,
+    {
+        use std::string::String;
+
+        let mut frame = String::new();
+        loop {
+            frame.clear();
+            // Clear the screen and move the cursor home.
+            frame.push_str("\x1b[2J\x1b[1;1H");
+            // Writing into a String cannot fail, and a failed read writes nothing.
+            if self.render(sensor, &mut frame).is_err() {
+                let _ = self.render_disconnected(&mut frame);
+            }
+            out.write_all(frame.as_bytes())?;
+            out.flush()?;
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::AS5600Driver;
+    use crate::mock::AS56Mock;
+    use crate::regs::regs;
+    use std::string::String;
+
+    #[test]
+    fn live_registers_come_from_one_snapshot() {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(1234);
+        let mut sensor = AS5600Driver::new(mock.clone());
+        let mut frame = String::new();
+        Dashboard::new()
+            .with_style(Style::Ascii)
+            .render(&mut sensor, &mut frame)
+            .unwrap();
+        assert!(frame.contains("1234"), "{frame}");
+        for reg in [
+            regs::STATUS,
+            regs::RAW_ANGLE_HI,
+            regs::ANGLE_HI,
+            regs::AGC,
+            regs::MAGNITUDE_HI,
+        ] {
+            mock.mock_assert_reads(reg, 1);
+        }
+    }
+
+    /// Renders one frame of `dashboard` from a fresh mock at raw angle 1024.
+    fn frame(dashboard: Dashboard) -> (AS56Mock, String) {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(1024);
+        mock.mock_clear_log();
+        let mut frame = String::new();
+        dashboard
+            .render(&mut AS5600Driver::new(mock.clone()), &mut frame)
+            .unwrap();
+        (mock, frame)
+    }
+
+    const MEMORY_REGISTERS: [u8; 4] = [regs::ZMCO, regs::ZPOS_HI, regs::MPOS_HI, regs::MANG_HI];
+
+    #[test]
+    fn reads_only_the_enabled_sections() {
+        let (mock, text) = frame(
+            Dashboard::new()
+                .with_style(Style::Ascii)
+                .with_sections(Sections::POSITION),
+        );
+        assert_eq!(
+            text,
+            concat!(
+                "+--------------------------------------------------------------+\n",
+                "|                 AS5600 FULL REGISTER MONITOR                 |\n",
+                "+--------------------------------------------------------------+\n",
+                "| POSITION DATA                                                |\n",
+                "|    Raw Angle: 1024 / 4095  25% [######.....................] |\n",
+                "|    Filtered:  1024 / 4095  25% [######.....................] |\n",
+                "+--------------------------------------------------------------+\n",
+            ),
+            "{text}"
+        );
+        mock.mock_assert_reads(regs::RAW_ANGLE_HI, 1);
+        mock.mock_assert_reads(regs::CONF_HI, 0);
+        for reg in MEMORY_REGISTERS {
+            mock.mock_assert_reads(reg, 0);
+        }
+
+        let config_only = Sections {
+            position: false,
+            magnet: false,
+            config: true,
+            memory: false,
+        };
+        let (mock, _) = frame(Dashboard::new().with_sections(config_only));
+        mock.mock_assert_reads(regs::CONF_HI, 1);
+        mock.mock_assert_reads(regs::STATUS, 0);
+        mock.mock_assert_reads(regs::RAW_ANGLE_HI, 0);
+        for reg in MEMORY_REGISTERS {
+            mock.mock_assert_reads(reg, 0);
+        }
+    }
+
+    #[test]
+    fn pairs_stack_on_narrow_frames() {
+        let magnet = Sections {
+            position: false,
+            magnet: true,
+            config: false,
+            memory: false,
+        };
+        let ascii = Dashboard::new()
+            .with_style(Style::Ascii)
+            .with_sections(magnet);
+
+        let (_, narrow) = frame(ascii.with_width(MIN_WIDTH));
+        assert_eq!(
+            narrow,
+            concat!(
+                "+----------------------------------------------+\n",
+                "|         AS5600 FULL REGISTER MONITOR         |\n",
+                "+----------------------------------------------+\n",
+                "| MAGNET DIAGNOSTICS                           |\n",
+                "|    Detected:        YES                      |\n",
+                "|    Field Status:    0x20 Raw                 |\n",
+                "|    Too Weak:        OK                       |\n",
+                "|    Too Strong:      OK                       |\n",
+                "|    Magnitude:       0                        |\n",
+                "|    AGC Value:       100                      |\n",
+                "+----------------------------------------------+\n",
+            ),
+            "{narrow}"
+        );
+        // Widths outside the supported range are clamped.
+        assert_eq!(frame(ascii.with_width(10)).1, narrow);
+
+        let (_, wide) = frame(ascii.with_width(72));
+        assert_eq!(
+            wide,
+            concat!(
+                "+----------------------------------------------------------------------+\n",
+                "|                     AS5600 FULL REGISTER MONITOR                     |\n",
+                "+----------------------------------------------------------------------+\n",
+                "| MAGNET DIAGNOSTICS                                                   |\n",
+                "|    Detected:        YES           Field Status:    0x20 Raw          |\n",
+                "|    Too Weak:        OK            Too Strong:      OK                |\n",
+                "|    Magnitude:       0             AGC Value:       100               |\n",
+                "+----------------------------------------------------------------------+\n",
+            ),
+            "{wide}"
+        );
+        let (_, widest) = frame(ascii.with_width(500));
+        assert!(
+            widest.lines().all(|line| line.len() == MAX_WIDTH),
+            "{widest}"
+        );
+    }
+
+    #[test]
+    fn emoji_frame_with_every_section() {
+        let (_, text) = frame(Dashboard::new());
+        assert_eq!(
+            text,
+            concat!(
+                "╔══════════════════════════════════════════════════════════════╗\n",
+                "║               🛰️  AS5600 FULL REGISTER MONITOR               ║\n",
+                "╠══════════════════════════════════════════════════════════════╣\n",
+                "║ 📍 POSITION DATA                                             ║\n",
+                "║    Raw Angle: 1024 / 4095  25% │██████▊                    │ ║\n",
+                "║    Filtered:  1024 / 4095  25% │██████▊                    │ ║\n",
+                "╠══════════════════════════════════════════════════════════════╣\n",
+                "║ 🧲 MAGNET DIAGNOSTICS                                        ║\n",
+                "║    Detected:        ✅ YES    Field Status:    0x20 Raw      ║\n",
+                "║    Too Weak:        ✅ OK     Too Strong:      ✅ OK         ║\n",
+                "║    Magnitude:       0         AGC Value:       100           ║\n",
+                "╠══════════════════════════════════════════════════════════════╣\n",
+                "║ ⚙️ CHIP CONFIGURATION (CONF)                                 ║\n",
+                "║    Watchdog:        ⚡ ON     Power Mode:      Nominal       ║\n",
+                "║    Hysteresis:      Off       Output Stage:    AnalogFull    ║\n",
+                "║    PWM Frequency:   Hz115     Slow Filter:     X16           ║\n",
+                "║    Fast Threshold:  SlowOnly                                 ║\n",
+                "╠══════════════════════════════════════════════════════════════╣\n",
+                "║ 💾 MEMORY & OPERATING RANGES                                 ║\n",
+                "║    Burn Cycles:     0/3       Zero Pos (ZPOS): 0             ║\n",
+                "║    Max Pos (MPOS):  0         Max Ang (MANG):  0             ║\n",
+                "╚══════════════════════════════════════════════════════════════╝\n",
+            ),
+            "{text}"
+        );
+    }
+}
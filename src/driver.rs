@@ -1,27 +1,141 @@
+use crate::error::AS56Error;
 use crate::regs::*;
 use crate::traits::AS5600Interface;
 use crate::types::*;
-use crate::error::AS56Error;
-use embedded_hal::i2c::{I2c, SevenBitAddress};
+use crate::variant::{AS5600, AS5600L, Variant};
+use core::marker::PhantomData;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{I2c, Operation, SevenBitAddress};
+
+/// Mask for the 12 significant bits of angle and position registers.
+pub(crate) const MASK_12BIT: u16 = 0x0FFF;
+
+/// Mask for the 14 significant bits of the CONF register pair.
+pub(crate) const MASK_CONF: u16 = 0x3FFF;
+
+/// Value written to `BURN` to permanently store ZPOS and MPOS.
+pub(crate) const BURN_ANGLE_CMD: u8 = 0x80;
+
+/// Value written to `BURN` to permanently store MANG and CONF.
+pub(crate) const BURN_SETTING_CMD: u8 = 0x40;
+
+/// Values written to `BURN`, in order, to reload the OTP content into the registers.
+pub(crate) const OTP_RELOAD_SEQUENCE: [u8; 3] = [0x01, 0x11, 0x10];
+
+/// Time the chip needs to complete an OTP burn (datasheet: at least 1 ms).
+pub(crate) const BURN_WAIT_US: u32 = 1_000;
 
 /// Main driver for the AS5600 sensor.
-pub struct AS5600Driver<I2C> {
+///
+/// The `V` parameter selects the chip variant ([`AS5600`] by default, or [`AS5600L`]).
+pub struct AS5600Driver<I2C, V = AS5600> {
     i2c: I2C,
     address: u8,
+    _variant: PhantomData<V>,
 }
 
 impl<I2C: I2c<SevenBitAddress>> AS5600Driver<I2C> {
     /// Creates a new driver instance with the default I2C address (0x36).
     pub fn new(i2c: I2C) -> Self {
+        Self::with_address(i2c, DEFAULT_ADDR)
+    }
+
+    /// Creates a new driver instance with a custom I2C address.
+    pub fn with_address(i2c: I2C, address: u8) -> Self {
         Self {
             i2c,
-            address: DEFAULT_ADDR,
+            address,
+            _variant: PhantomData,
         }
     }
+}
 
-    /// Creates a new driver instance with a custom I2C address.
-    pub fn with_address(i2c: I2C, address: u8) -> Self {
-        Self { i2c, address }
+impl<I2C: I2c<SevenBitAddress>> AS5600Driver<I2C, AS5600L> {
+    /// Creates a new AS5600L driver instance with the factory default I2C address (0x40).
+    pub fn new_as5600l(i2c: I2C) -> Self {
+        Self::as5600l_with_address(i2c, AS5600L::DEFAULT_ADDR)
+    }
+
+    /// Creates a new AS5600L driver instance with a previously programmed I2C address.
+    pub fn as5600l_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            _variant: PhantomData,
+        }
+    }
+
+    /// Reads the 7-bit address stored in the I2C_ADDR register.
+    pub fn get_i2c_address(&mut self) -> Result<u8, AS56Error<I2C::Error>> {
+        Ok(self.read_u8(regs::I2C_ADDR)? >> 1)
+    }
+
+    /// Moves the chip to a new I2C address at runtime and re-targets the driver.
+    ///
+    /// Writes the address to I2C_ADDR, activates it through I2C_UPDT and confirms
+    /// that the chip answers at the new address. Once I2C_UPDT is written the driver
+    /// targets the new address; if nothing acknowledges it there, the error is
+    /// [`AS56Error::AddressNotConfirmed`]. Other bus errors of the confirmation are
+    /// returned as they are, and a device answering with another I2C_ADDR is
+    /// reported as [`AS56Error::ImplausibleRegister`]. The change is volatile until
+    /// burned with [`danger_permanent_burn_address`](Self::danger_permanent_burn_address).
+    pub fn change_address(&mut self, new_address: u8) -> Result<(), AS56Error<I2C::Error>> {
+        if !is_valid_address(new_address) {
+            return Err(AS56Error::InvalidAddress(new_address));
+        }
+        let current = self.read_u8(regs::I2C_ADDR)?;
+        let value = encode_i2c_addr(current, new_address);
+        self.write_u8(regs::I2C_ADDR, value)?;
+        self.write_u8(regs::I2C_UPDT, value)?;
+
+        // The chip switched on the I2C_UPDT write, so the old address is gone.
+        self.address = new_address;
+        match self.read_u8(regs::I2C_ADDR) {
+            Ok(stored) if stored >> 1 == new_address => Ok(()),
+            Ok(stored) => Err(AS56Error::ImplausibleRegister {
+                register: regs::I2C_ADDR,
+                value: stored,
+            }),
+            Err(AS56Error::DeviceNotResponding(_)) => {
+                Err(AS56Error::AddressNotConfirmed(new_address))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// **DANGER**: Permanently burns the current I2C address to the chip.
+    ///
+    /// Uses the configuration burn (0x40), so MANG and CONF are burned together with
+    /// I2C_ADDR. The burn is refused with [`AS56Error::NothingToBurn`] while I2C_ADDR
+    /// still holds the factory default (0x40), with [`AS56Error::InvalidAddress`]
+    /// unless it holds the address the driver is currently talking to, and with the checks of
+    /// [`danger_verified_burn_config`](Self::danger_verified_burn_config). After the
+    /// burn the OTP is reloaded and MANG, CONF and I2C_ADDR are verified.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
+    pub unsafe fn danger_permanent_burn_address(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let stored = self.get_i2c_address()?;
+        if stored == AS5600L::DEFAULT_ADDR {
+            return Err(AS56Error::NothingToBurn);
+        }
+        if stored != self.address {
+            return Err(AS56Error::InvalidAddress(stored));
+        }
+        unsafe { self.danger_verified_burn_config(delay)? };
+        let burned = self.get_i2c_address()?;
+        verify_otp(regs::I2C_ADDR, stored as u16, burned as u16)
+    }
+}
+
+impl<I2C: I2c<SevenBitAddress>, V: Variant> AS5600Driver<I2C, V> {
+    /// Returns the I2C address the driver is talking to.
+    pub fn address(&self) -> u8 {
+        self.address
     }
 
     /// Internal helper to read a single byte from a register.
@@ -29,7 +143,7 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Driver<I2C> {
         let mut buf = [0u8; 1];
         self.i2c
             .write_read(self.address, &[reg], &mut buf)
-            .map_err(AS56Error::I2c)?;
+            .map_err(AS56Error::bus(self.address))?;
         Ok(buf[0])
     }
 
@@ -37,7 +151,7 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Driver<I2C> {
     fn write_u8(&mut self, reg: u8, value: u8) -> Result<(), AS56Error<I2C::Error>> {
         self.i2c
             .write(self.address, &[reg, value])
-            .map_err(AS56Error::I2c)?;
+            .map_err(AS56Error::bus(self.address))?;
         Ok(())
     }
 
@@ -46,37 +160,124 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Driver<I2C> {
         let mut buf = [0u8; 2];
         self.i2c
             .write_read(self.address, &[reg_hi], &mut buf)
-            .map_err(AS56Error::I2c)?;
-        Ok(u16::from_be_bytes(buf) & 0x0FFF)
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(decode_u16(buf))
     }
 
     /// Internal helper to write a 12-bit value to two consecutive registers.
     fn write_u16(&mut self, reg_hi: u8, value: u16) -> Result<(), AS56Error<I2C::Error>> {
-        let bytes = value.to_be_bytes();
         self.i2c
-            .write(self.address, &[reg_hi, bytes[0], bytes[1]])
-            .map_err(AS56Error::I2c)?;
+            .write(self.address, &encode_u16(reg_hi, value))
+            .map_err(AS56Error::bus(self.address))?;
         Ok(())
     }
 
     /// **DANGER**: Permanently burns ZPOS and MPOS settings to the chip.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed at most 3 times
+    /// over the lifetime of the chip.
     pub unsafe fn danger_permanent_burn_settings(&mut self) -> Result<(), AS56Error<I2C::Error>> {
         self.i2c
-            .write(self.address, &[regs::BURN, 0x80])
-            .map_err(AS56Error::I2c)?;
+            .write(self.address, &[regs::BURN, BURN_ANGLE_CMD])
+            .map_err(AS56Error::bus(self.address))?;
         Ok(())
     }
 
     /// **DANGER**: Permanently burns Configuration settings to the chip.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
     pub unsafe fn danger_permanent_burn_config(&mut self) -> Result<(), AS56Error<I2C::Error>> {
         self.i2c
-            .write(self.address, &[regs::BURN, 0x40])
-            .map_err(AS56Error::I2c)?;
+            .write(self.address, &[regs::BURN, BURN_SETTING_CMD])
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(())
+    }
+
+    /// Reloads the OTP content into the ZPOS, MPOS, MANG and CONF registers.
+    ///
+    /// Any volatile changes made with the `set_*` methods are replaced by the burned values.
+    pub fn reload_otp(&mut self) -> Result<(), AS56Error<I2C::Error>> {
+        for cmd in OTP_RELOAD_SEQUENCE {
+            self.write_u8(regs::BURN, cmd)?;
+        }
         Ok(())
     }
+
+    /// **DANGER**: Permanently burns ZPOS and MPOS after checking the datasheet
+    /// preconditions, then reloads the OTP and verifies the burned values.
+    ///
+    /// The burn is refused with [`AS56Error::MagnetNotDetected`],
+    /// [`AS56Error::BurnLimitReached`] or [`AS56Error::NothingToBurn`] before anything
+    /// is written. A readback that differs from the programmed values is reported as
+    /// [`AS56Error::OtpMismatch`].
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed at most 3 times
+    /// over the lifetime of the chip.
+    pub unsafe fn danger_verified_burn_settings(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let status = self.read_u8(regs::STATUS)?;
+        let zmco = self.read_u8(regs::ZMCO)? & 0x03;
+        let zpos = self.read_u16(regs::ZPOS_HI)?;
+        let mpos = self.read_u16(regs::MPOS_HI)?;
+        check_angle_burn(status, zmco, zpos, mpos)?;
+
+        unsafe { self.danger_permanent_burn_settings()? };
+        delay.delay_us(BURN_WAIT_US);
+        self.reload_otp()?;
+
+        verify_otp(regs::ZPOS_HI, zpos, self.read_u16(regs::ZPOS_HI)?)?;
+        verify_otp(regs::MPOS_HI, mpos, self.read_u16(regs::MPOS_HI)?)
+    }
+
+    /// **DANGER**: Permanently burns MANG and CONF after checking the datasheet
+    /// preconditions, then reloads the OTP and verifies the burned values.
+    ///
+    /// The burn is refused with [`AS56Error::BurnLimitReached`] once ZPOS/MPOS have
+    /// been burned (ZMCO is non-zero). A readback that differs from the programmed
+    /// values is reported as [`AS56Error::OtpMismatch`].
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
+    pub unsafe fn danger_verified_burn_config(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let zmco = self.read_u8(regs::ZMCO)? & 0x03;
+        check_setting_burn(zmco)?;
+        let mang = self.read_u16(regs::MANG_HI)?;
+        let conf = self.read_conf_word()?;
+
+        unsafe { self.danger_permanent_burn_config()? };
+        delay.delay_us(BURN_WAIT_US);
+        self.reload_otp()?;
+
+        verify_otp(regs::MANG_HI, mang, self.read_u16(regs::MANG_HI)?)?;
+        verify_otp(regs::CONF_HI, conf, self.read_conf_word()?)
+    }
+
+    /// Internal helper to read the 14 significant bits of CONF as one word.
+    fn read_conf_word(&mut self) -> Result<u16, AS56Error<I2C::Error>> {
+        Ok(u16::from_be_bytes(self.read_conf_raw()?) & MASK_CONF)
+    }
+
+    /// Internal helper to read CONF_HI and CONF_LO in one transfer.
+    fn read_conf_raw(&mut self) -> Result<[u8; 2], AS56Error<I2C::Error>> {
+        let mut buf = [0u8; 2];
+        self.i2c
+            .write_read(self.address, &[regs::CONF_HI], &mut buf)
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(buf)
+    }
 }
 
-impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
+impl<I2C: I2c<SevenBitAddress>, V: Variant> AS5600Interface for AS5600Driver<I2C, V> {
     type Error = I2C::Error;
 
     fn read_raw_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
@@ -97,11 +298,7 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
 
     fn get_magnet_status(&mut self) -> Result<MagnetStatus, AS56Error<Self::Error>> {
         let val = self.read_u8(regs::STATUS)?;
-        Ok(MagnetStatus {
-            detected: (val & 0x20) != 0,
-            too_weak: (val & 0x10) != 0,
-            too_strong: (val & 0x08) != 0,
-        })
+        check_status(val)
     }
 
     fn get_magnitude(&mut self) -> Result<u16, AS56Error<Self::Error>> {
@@ -112,66 +309,48 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
         self.read_u8(regs::AGC)
     }
 
+    fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        // STATUS..ANGLE_LO is contiguous; AGC..MAGNITUDE_LO follows after reserved
+        // registers. Both blocks are fetched in a single bus transaction.
+        let mut live = [0u8; SNAPSHOT_LIVE_LEN];
+        let mut field = [0u8; SNAPSHOT_FIELD_LEN];
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    Operation::Write(&[regs::STATUS]),
+                    Operation::Read(&mut live),
+                    Operation::Write(&[regs::AGC]),
+                    Operation::Read(&mut field),
+                ],
+            )
+            .map_err(AS56Error::bus(self.address))?;
+        check_status(live[0])?;
+        Ok(decode_snapshot(live, field))
+    }
+
     fn get_config(&mut self) -> Result<Configuration, AS56Error<Self::Error>> {
         let hi = self.read_u8(regs::CONF_HI)?;
         let lo = self.read_u8(regs::CONF_LO)?;
-
-        Ok(Configuration {
-            power_mode: match lo & 0x03 {
-                0b01 => PowerMode::LPM1,
-                0b10 => PowerMode::LPM2,
-                0b11 => PowerMode::LPM3,
-                _ => PowerMode::Nominal,
-            },
-            hysteresis: match (lo >> 2) & 0x03 {
-                0b01 => Hysteresis::Lsb1,
-                0b10 => Hysteresis::Lsb2,
-                0b11 => Hysteresis::Lsb3,
-                _ => Hysteresis::Off,
-            },
-            output_stage: match (lo >> 4) & 0x03 {
-                0b01 => OutputStage::AnalogReduced,
-                0b10 => OutputStage::PWM,
-                _ => OutputStage::AnalogFull,
-            },
-            pwm_frequency: match (lo >> 6) & 0x03 {
-                0b01 => PwmFrequency::Hz230,
-                0b10 => PwmFrequency::Hz460,
-                0b11 => PwmFrequency::Hz920,
-                _ => PwmFrequency::Hz115,
-            },
-            slow_filter: match hi & 0x03 {
-                0b01 => SlowFilter::X8,
-                0b10 => SlowFilter::X4,
-                0b11 => SlowFilter::X2,
-                _ => SlowFilter::X16,
-            },
-            fast_filter_threshold: match (hi >> 2) & 0x07 {
-                0b001 => FastFilterThreshold::Lsb6,
-                0b010 => FastFilterThreshold::Lsb7,
-                0b011 => FastFilterThreshold::Lsb9,
-                0b100 => FastFilterThreshold::Lsb18,
-                0b101 => FastFilterThreshold::Lsb21,
-                0b110 => FastFilterThreshold::Lsb24,
-                0b111 => FastFilterThreshold::Lsb10,
-                _ => FastFilterThreshold::SlowOnly,
-            },
-            watchdog: (hi & 0x20) != 0,
-        })
+        Ok(Configuration::from_registers(hi, lo)?)
     }
 
     fn set_config(&mut self, config: Configuration) -> Result<(), AS56Error<Self::Error>> {
-        let hi = ((config.watchdog as u8) << 5)
-            | ((config.fast_filter_threshold as u8) << 2)
-            | (config.slow_filter as u8);
-
-        let lo = ((config.pwm_frequency as u8) << 6)
-            | ((config.output_stage as u8) << 4)
-            | ((config.hysteresis as u8) << 2)
-            | (config.power_mode as u8);
+        let (hi, lo) = config.to_registers();
+        self.i2c
+            .write(self.address, &[regs::CONF_HI, hi, lo])
+            .map_err(AS56Error::bus(self.address))
+    }
 
-        self.write_u8(regs::CONF_HI, hi)?;
-        self.write_u8(regs::CONF_LO, lo)?;
+    fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>> {
+        if let Some(buf) = modify_conf(self.read_conf_raw()?, f)? {
+            self.i2c
+                .write(self.address, &buf)
+                .map_err(AS56Error::bus(self.address))?;
+        }
         Ok(())
     }
 
@@ -180,7 +359,7 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
     }
 
     fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
-        self.write_u16(regs::ZPOS_HI, angle & 0x0FFF)
+        self.write_u16(regs::ZPOS_HI, check_12bit(angle)?)
     }
 
     fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
@@ -188,7 +367,7 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
     }
 
     fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
-        self.write_u16(regs::MPOS_HI, angle & 0x0FFF)
+        self.write_u16(regs::MPOS_HI, check_12bit(angle)?)
     }
 
     fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
@@ -196,6 +375,271 @@ impl<I2C: I2c<SevenBitAddress>> AS5600Interface for AS5600Driver<I2C> {
     }
 
     fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
-        self.write_u16(regs::MANG_HI, angle & 0x0FFF)
+        self.write_u16(regs::MANG_HI, check_12bit(angle)?)
+    }
+}
+
+// --- Register encoding shared by the blocking and async drivers ---
+
+/// Decodes a 12-bit value from a big-endian HI/LO register pair.
+pub(crate) fn decode_u16(buf: [u8; 2]) -> u16 {
+    u16::from_be_bytes(buf) & MASK_12BIT
+}
+
+/// Builds the write buffer for a 12-bit value starting at `reg_hi`.
+pub(crate) fn encode_u16(reg_hi: u8, value: u16) -> [u8; 3] {
+    let bytes = value.to_be_bytes();
+    [reg_hi, bytes[0], bytes[1]]
+}
+
+/// Decodes the magnet flags (MD, ML, MH) from the STATUS register.
+pub(crate) fn decode_magnet_status(val: u8) -> MagnetStatus {
+    MagnetStatus {
+        detected: (val & 0x20) != 0,
+        too_weak: (val & 0x10) != 0,
+        too_strong: (val & 0x08) != 0,
+    }
+}
+
+/// Decodes STATUS, rejecting the impossible combination of ML and MH both set.
+pub(crate) fn check_status<E>(val: u8) -> Result<MagnetStatus, AS56Error<E>> {
+    let status = decode_magnet_status(val);
+    if status.too_weak && status.too_strong {
+        return Err(AS56Error::ImplausibleRegister {
+            register: regs::STATUS,
+            value: val,
+        });
+    }
+    Ok(status)
+}
+
+/// Rejects values that do not fit a 12-bit register.
+pub(crate) fn check_12bit<E>(value: u16) -> Result<u16, AS56Error<E>> {
+    if value > MASK_12BIT {
+        return Err(AS56Error::OutOfRange {
+            value,
+            max: MASK_12BIT,
+        });
+    }
+    Ok(value)
+}
+
+/// Length of the STATUS..ANGLE_LO block read by `read_snapshot`.
+pub(crate) const SNAPSHOT_LIVE_LEN: usize = (regs::ANGLE_LO - regs::STATUS + 1) as usize;
+
+/// Length of the AGC..MAGNITUDE_LO block read by `read_snapshot`.
+pub(crate) const SNAPSHOT_FIELD_LEN: usize = (regs::MAGNITUDE_LO - regs::AGC + 1) as usize;
+
+/// Decodes the two register blocks read by `read_snapshot`.
+pub(crate) fn decode_snapshot(
+    live: [u8; SNAPSHOT_LIVE_LEN],
+    field: [u8; SNAPSHOT_FIELD_LEN],
+) -> Snapshot {
+    Snapshot {
+        status: decode_magnet_status(live[0]),
+        status_raw: live[0],
+        raw_angle: decode_u16([live[1], live[2]]),
+        angle: decode_u16([live[3], live[4]]),
+        agc: field[0],
+        magnitude: decode_u16([field[1], field[2]]),
+    }
+}
+
+/// Applies `f` to the configuration held in `[CONF_HI, CONF_LO]`.
+///
+/// Returns the write buffer for the new CONF values, or `None` if nothing changed.
+/// The unused CONF_HI bits are carried over unless `f` changes them.
+pub(crate) fn modify_conf<E>(
+    current: [u8; 2],
+    f: impl FnOnce(&mut Configuration),
+) -> Result<Option<[u8; 3]>, AS56Error<E>> {
+    let mut config = Configuration::from_registers(current[0], current[1])?;
+    f(&mut config);
+    let (hi, lo) = config.to_registers();
+    if [hi, lo] == current {
+        return Ok(None);
+    }
+    Ok(Some([regs::CONF_HI, hi, lo]))
+}
+
+/// Checks the datasheet preconditions for burning ZPOS and MPOS.
+pub(crate) fn check_angle_burn<E>(
+    status: u8,
+    zmco: u8,
+    zpos: u16,
+    mpos: u16,
+) -> Result<(), AS56Error<E>> {
+    if !decode_magnet_status(status).detected {
+        return Err(AS56Error::MagnetNotDetected);
+    }
+    if zmco >= 3 {
+        return Err(AS56Error::BurnLimitReached(zmco));
+    }
+    if zpos == 0 && mpos == 0 {
+        return Err(AS56Error::NothingToBurn);
+    }
+    Ok(())
+}
+
+/// Checks the datasheet preconditions for burning MANG and CONF.
+///
+/// The setting burn is only possible while ZPOS/MPOS have never been burned.
+pub(crate) fn check_setting_burn<E>(zmco: u8) -> Result<(), AS56Error<E>> {
+    if zmco != 0 {
+        return Err(AS56Error::BurnLimitReached(zmco));
+    }
+    Ok(())
+}
+
+/// Compares a value loaded back from OTP with the one that was programmed.
+pub(crate) fn verify_otp<E>(register: u8, expected: u16, actual: u16) -> Result<(), AS56Error<E>> {
+    if expected != actual {
+        return Err(AS56Error::OtpMismatch {
+            register,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Returns `true` for 7-bit addresses outside the reserved ranges (0x00-0x07, 0x78-0x7F).
+pub(crate) fn is_valid_address(address: u8) -> bool {
+    (0x08..=0x77).contains(&address)
+}
+
+/// Places a 7-bit address in bits 7:1 of I2C_ADDR, keeping the reserved bit 0.
+pub(crate) fn encode_i2c_addr(current: u8, address: u8) -> u8 {
+    (address << 1) | (current & 0x01)
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{AS56Mock, FaultAction, MockError};
+    use embedded_hal::i2c::ErrorKind;
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    #[test]
+    fn change_address_targets_the_new_address() {
+        let mock = AS56Mock::new_as5600l();
+        let mut sensor = AS5600Driver::new_as5600l(mock.clone());
+        sensor.change_address(0x41).unwrap();
+        assert_eq!(sensor.address(), 0x41);
+        assert_eq!(mock.mock_address(), 0x41);
+        mock.mock_assert_write(regs::I2C_UPDT, &[0x82]);
+        assert_eq!(sensor.get_i2c_address().unwrap(), 0x41);
+
+        // The chip no longer answers at the factory address.
+        let mut old = AS5600Driver::new_as5600l(mock.clone());
+        assert_eq!(
+            old.get_i2c_address(),
+            Err(AS56Error::DeviceNotResponding(0x40))
+        );
+
+        // Without a burn the change is lost on a power cycle.
+        mock.mock_power_cycle();
+        assert_eq!(
+            sensor.get_i2c_address(),
+            Err(AS56Error::DeviceNotResponding(0x41))
+        );
+        assert_eq!(old.get_i2c_address().unwrap(), 0x40);
+    }
+
+    #[test]
+    fn unconfirmed_address_change_keeps_the_new_address() {
+        let mock = AS56Mock::new_as5600l();
+        let mut sensor = AS5600Driver::new_as5600l(mock.clone());
+        // Read I2C_ADDR, write I2C_ADDR, write I2C_UPDT, then the confirmation read.
+        mock.mock_fail_nth(4, FaultAction::NACK_ADDRESS);
+        let err = sensor.change_address(0x41).unwrap_err();
+        assert_eq!(err, AS56Error::AddressNotConfirmed(0x41));
+        assert_eq!(err.kind(), crate::error::ErrorKind::NotResponding);
+        assert_eq!(sensor.address(), 0x41);
+        mock.mock_assert_write(regs::I2C_UPDT, &[0x82]);
+    }
+
+    #[test]
+    fn failed_address_confirmation_is_not_a_missing_chip() {
+        let mock = AS56Mock::new_as5600l();
+        let mut sensor = AS5600Driver::new_as5600l(mock.clone());
+        mock.mock_fail_nth(4, FaultAction::BUS_ERROR);
+        let err = sensor.change_address(0x41).unwrap_err();
+        assert_eq!(err, AS56Error::I2c(MockError::Injected(ErrorKind::Bus)));
+
+        // Something answers at the new address, but with another I2C_ADDR.
+        mock.mock_fail_nth(4, FaultAction::Corrupt(0x04));
+        let err = sensor.change_address(0x42).unwrap_err();
+        assert_eq!(
+            err,
+            AS56Error::ImplausibleRegister {
+                register: regs::I2C_ADDR,
+                value: 0x80
+            }
+        );
+    }
+
+    #[test]
+    fn address_burn_is_verified() {
+        let mock = AS56Mock::new_as5600l();
+        let mut sensor = AS5600Driver::new_as5600l(mock.clone());
+        // A factory chip still holds the default address.
+        let err = unsafe { sensor.danger_permanent_burn_address(&mut NoDelay) }.unwrap_err();
+        assert_eq!(err, AS56Error::NothingToBurn);
+
+        // I2C_ADDR written without I2C_UPDT: the driver still talks to 0x40.
+        mock.clone()
+            .write(AS5600L_DEFAULT_ADDR, &[regs::I2C_ADDR, 0x84])
+            .unwrap();
+        let err = unsafe { sensor.danger_permanent_burn_address(&mut NoDelay) }.unwrap_err();
+        assert_eq!(err, AS56Error::InvalidAddress(0x42));
+        mock.mock_assert_no_write(regs::BURN);
+
+        sensor.change_address(0x41).unwrap();
+        unsafe { sensor.danger_permanent_burn_address(&mut NoDelay) }.unwrap();
+        assert!(mock.mock_otp().settings_burned);
+        assert_eq!(mock.mock_otp().i2c_addr, 0x82);
+        mock.mock_assert_write(regs::BURN, &[OTP_RELOAD_SEQUENCE[2]]);
+
+        // The burned address survives a power cycle.
+        mock.mock_power_cycle();
+        assert_eq!(mock.mock_address(), 0x41);
+        assert_eq!(sensor.get_i2c_address().unwrap(), 0x41);
+    }
+
+    #[test]
+    fn address_burn_checks_the_burn_counter() {
+        let mock = AS56Mock::new_as5600l();
+        let mut sensor = AS5600Driver::new_as5600l(mock.clone());
+        sensor.set_zero_position(100).unwrap();
+        unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+        sensor.change_address(0x41).unwrap();
+        mock.mock_clear_log();
+
+        let err = unsafe { sensor.danger_permanent_burn_address(&mut NoDelay) }.unwrap_err();
+        assert_eq!(err, AS56Error::BurnLimitReached(1));
+        mock.mock_assert_no_write(regs::BURN);
+    }
+
+    #[test]
+    fn unused_conf_bits_are_read_and_written_back() {
+        let mut mock = AS56Mock::new();
+        mock.write(DEFAULT_ADDR, &[regs::CONF_HI, 0xE3, 0x01])
+            .unwrap();
+        let mut sensor = AS5600Driver::new(mock.clone());
+        let config = sensor.get_config().unwrap();
+        assert_eq!(config.unused_bits, 0b11);
+        assert_eq!(config.slow_filter, SlowFilter::X2);
+
+        mock.mock_clear_log();
+        sensor.set_config(config).unwrap();
+        mock.mock_assert_write(regs::CONF_HI, &[0xE3, 0x01]);
+        sensor.set_power_mode(PowerMode::LPM2).unwrap();
+        mock.mock_assert_write(regs::CONF_HI, &[0xE3, 0x02]);
     }
 }
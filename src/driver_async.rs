@@ -0,0 +1,516 @@
+use crate::driver::*;
+use crate::error::AS56Error;
+use crate::regs::*;
+use crate::traits::AS5600InterfaceAsync;
+use crate::types::*;
+use crate::variant::{AS5600, AS5600L, Variant};
+use core::marker::PhantomData;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::{I2c, Operation, SevenBitAddress};
+
+/// Async driver for the AS5600 sensor, built on `embedded-hal-async`.
+///
+/// Mirrors [`AS5600Driver`] method for method, but every
+/// bus transaction is awaited so it does not block the executor (e.g. Embassy).
+///
+/// The `V` parameter selects the chip variant ([`AS5600`] by default, or [`AS5600L`]).
+pub struct AS5600DriverAsync<I2C, V = AS5600> {
+    i2c: I2C,
+    address: u8,
+    _variant: PhantomData<V>,
+}
+
+impl<I2C: I2c<SevenBitAddress>> AS5600DriverAsync<I2C> {
+    /// Creates a new driver instance with the default I2C address (0x36).
+    pub fn new(i2c: I2C) -> Self {
+        Self::with_address(i2c, DEFAULT_ADDR)
+    }
+
+    /// Creates a new driver instance with a custom I2C address.
+    pub fn with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            _variant: PhantomData,
+        }
+    }
+}
+
+impl<I2C: I2c<SevenBitAddress>> AS5600DriverAsync<I2C, AS5600L> {
+    /// Creates a new AS5600L driver instance with the factory default I2C address (0x40).
+    pub fn new_as5600l(i2c: I2C) -> Self {
+        Self::as5600l_with_address(i2c, AS5600L::DEFAULT_ADDR)
+    }
+
+    /// Creates a new AS5600L driver instance with a previously programmed I2C address.
+    pub fn as5600l_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            _variant: PhantomData,
+        }
+    }
+
+    /// Reads the 7-bit address stored in the I2C_ADDR register.
+    pub async fn get_i2c_address(&mut self) -> Result<u8, AS56Error<I2C::Error>> {
+        Ok(self.read_u8(regs::I2C_ADDR).await? >> 1)
+    }
+
+    /// Moves the chip to a new I2C address at runtime and re-targets the driver.
+    ///
+    /// Writes the address to I2C_ADDR, activates it through I2C_UPDT and confirms
+    /// that the chip answers at the new address. Once I2C_UPDT is written the driver
+    /// targets the new address; if nothing acknowledges it there, the error is
+    /// [`AS56Error::AddressNotConfirmed`]. Other bus errors of the confirmation are
+    /// returned as they are, and a device answering with another I2C_ADDR is
+    /// reported as [`AS56Error::ImplausibleRegister`]. The change is volatile until
+    /// burned with [`danger_permanent_burn_address`](Self::danger_permanent_burn_address).
+    pub async fn change_address(&mut self, new_address: u8) -> Result<(), AS56Error<I2C::Error>> {
+        if !is_valid_address(new_address) {
+            return Err(AS56Error::InvalidAddress(new_address));
+        }
+        let current = self.read_u8(regs::I2C_ADDR).await?;
+        let value = encode_i2c_addr(current, new_address);
+        self.write_u8(regs::I2C_ADDR, value).await?;
+        self.write_u8(regs::I2C_UPDT, value).await?;
+
+        // The chip switched on the I2C_UPDT write, so the old address is gone.
+        self.address = new_address;
+        match self.read_u8(regs::I2C_ADDR).await {
+            Ok(stored) if stored >> 1 == new_address => Ok(()),
+            Ok(stored) => Err(AS56Error::ImplausibleRegister {
+                register: regs::I2C_ADDR,
+                value: stored,
+            }),
+            Err(AS56Error::DeviceNotResponding(_)) => {
+                Err(AS56Error::AddressNotConfirmed(new_address))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// **DANGER**: Permanently burns the current I2C address to the chip.
+    ///
+    /// Uses the configuration burn (0x40), so MANG and CONF are burned together with
+    /// I2C_ADDR. The burn is refused with [`AS56Error::NothingToBurn`] while I2C_ADDR
+    /// still holds the factory default (0x40), with [`AS56Error::InvalidAddress`]
+    /// unless it holds the address the driver is currently talking to, and with the checks of
+    /// [`danger_verified_burn_config`](Self::danger_verified_burn_config). After the
+    /// burn the OTP is reloaded and MANG, CONF and I2C_ADDR are verified.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
+    pub async unsafe fn danger_permanent_burn_address(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let stored = self.get_i2c_address().await?;
+        if stored == AS5600L::DEFAULT_ADDR {
+            return Err(AS56Error::NothingToBurn);
+        }
+        if stored != self.address {
+            return Err(AS56Error::InvalidAddress(stored));
+        }
+        unsafe { self.danger_verified_burn_config(delay).await? };
+        let burned = self.get_i2c_address().await?;
+        verify_otp(regs::I2C_ADDR, stored as u16, burned as u16)
+    }
+}
+
+impl<I2C: I2c<SevenBitAddress>, V: Variant> AS5600DriverAsync<I2C, V> {
+    /// Returns the I2C address the driver is talking to.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Internal helper to read a single byte from a register.
+    async fn read_u8(&mut self, reg: u8) -> Result<u8, AS56Error<I2C::Error>> {
+        let mut buf = [0u8; 1];
+        self.i2c
+            .write_read(self.address, &[reg], &mut buf)
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(buf[0])
+    }
+
+    /// Internal helper to write a single byte to a register.
+    async fn write_u8(&mut self, reg: u8, value: u8) -> Result<(), AS56Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &[reg, value])
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(())
+    }
+
+    /// Internal helper to read a 12-bit value from two consecutive registers.
+    async fn read_u16(&mut self, reg_hi: u8) -> Result<u16, AS56Error<I2C::Error>> {
+        let mut buf = [0u8; 2];
+        self.i2c
+            .write_read(self.address, &[reg_hi], &mut buf)
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(decode_u16(buf))
+    }
+
+    /// Internal helper to write a 12-bit value to two consecutive registers.
+    async fn write_u16(&mut self, reg_hi: u8, value: u16) -> Result<(), AS56Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &encode_u16(reg_hi, value))
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(())
+    }
+
+    /// **DANGER**: Permanently burns ZPOS and MPOS settings to the chip.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed at most 3 times
+    /// over the lifetime of the chip.
+    pub async unsafe fn danger_permanent_burn_settings(
+        &mut self,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &[regs::BURN, BURN_ANGLE_CMD])
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(())
+    }
+
+    /// **DANGER**: Permanently burns Configuration settings to the chip.
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
+    pub async unsafe fn danger_permanent_burn_config(
+        &mut self,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &[regs::BURN, BURN_SETTING_CMD])
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(())
+    }
+
+    /// Reloads the OTP content into the ZPOS, MPOS, MANG and CONF registers.
+    ///
+    /// Any volatile changes made with the `set_*` methods are replaced by the burned values.
+    pub async fn reload_otp(&mut self) -> Result<(), AS56Error<I2C::Error>> {
+        for cmd in OTP_RELOAD_SEQUENCE {
+            self.write_u8(regs::BURN, cmd).await?;
+        }
+        Ok(())
+    }
+
+    /// **DANGER**: Permanently burns ZPOS and MPOS after checking the datasheet
+    /// preconditions, then reloads the OTP and verifies the burned values.
+    ///
+    /// See [`AS5600Driver::danger_verified_burn_settings`](crate::AS5600Driver::danger_verified_burn_settings).
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed at most 3 times
+    /// over the lifetime of the chip.
+    pub async unsafe fn danger_verified_burn_settings(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let status = self.read_u8(regs::STATUS).await?;
+        let zmco = self.read_u8(regs::ZMCO).await? & 0x03;
+        let zpos = self.read_u16(regs::ZPOS_HI).await?;
+        let mpos = self.read_u16(regs::MPOS_HI).await?;
+        check_angle_burn(status, zmco, zpos, mpos)?;
+
+        unsafe { self.danger_permanent_burn_settings().await? };
+        delay.delay_us(BURN_WAIT_US).await;
+        self.reload_otp().await?;
+
+        verify_otp(regs::ZPOS_HI, zpos, self.read_u16(regs::ZPOS_HI).await?)?;
+        verify_otp(regs::MPOS_HI, mpos, self.read_u16(regs::MPOS_HI).await?)
+    }
+
+    /// **DANGER**: Permanently burns MANG and CONF after checking the datasheet
+    /// preconditions, then reloads the OTP and verifies the burned values.
+    ///
+    /// See [`AS5600Driver::danger_verified_burn_config`](crate::AS5600Driver::danger_verified_burn_config).
+    ///
+    /// # Safety
+    /// This is an irreversible OTP write. It can be performed only once
+    /// over the lifetime of the chip.
+    pub async unsafe fn danger_verified_burn_config(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AS56Error<I2C::Error>> {
+        let zmco = self.read_u8(regs::ZMCO).await? & 0x03;
+        check_setting_burn(zmco)?;
+        let mang = self.read_u16(regs::MANG_HI).await?;
+        let conf = self.read_conf_word().await?;
+
+        unsafe { self.danger_permanent_burn_config().await? };
+        delay.delay_us(BURN_WAIT_US).await;
+        self.reload_otp().await?;
+
+        verify_otp(regs::MANG_HI, mang, self.read_u16(regs::MANG_HI).await?)?;
+        verify_otp(regs::CONF_HI, conf, self.read_conf_word().await?)
+    }
+
+    /// Internal helper to read the 14 significant bits of CONF as one word.
+    async fn read_conf_word(&mut self) -> Result<u16, AS56Error<I2C::Error>> {
+        Ok(u16::from_be_bytes(self.read_conf_raw().await?) & MASK_CONF)
+    }
+
+    /// Internal helper to read CONF_HI and CONF_LO in one transfer.
+    async fn read_conf_raw(&mut self) -> Result<[u8; 2], AS56Error<I2C::Error>> {
+        let mut buf = [0u8; 2];
+        self.i2c
+            .write_read(self.address, &[regs::CONF_HI], &mut buf)
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        Ok(buf)
+    }
+}
+
+impl<I2C: I2c<SevenBitAddress>, V: Variant> AS5600InterfaceAsync for AS5600DriverAsync<I2C, V> {
+    type Error = I2C::Error;
+
+    async fn read_raw_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::RAW_ANGLE_HI).await
+    }
+
+    async fn read_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::ANGLE_HI).await
+    }
+
+    async fn get_burn_count(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        Ok(self.read_u8(regs::ZMCO).await? & 0x03)
+    }
+
+    async fn get_status_raw(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.read_u8(regs::STATUS).await
+    }
+
+    async fn get_magnet_status(&mut self) -> Result<MagnetStatus, AS56Error<Self::Error>> {
+        let val = self.read_u8(regs::STATUS).await?;
+        check_status(val)
+    }
+
+    async fn get_magnitude(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::MAGNITUDE_HI).await
+    }
+
+    async fn get_agc(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.read_u8(regs::AGC).await
+    }
+
+    async fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        // STATUS..ANGLE_LO is contiguous; AGC..MAGNITUDE_LO follows after reserved
+        // registers. Both blocks are fetched in a single bus transaction.
+        let mut live = [0u8; SNAPSHOT_LIVE_LEN];
+        let mut field = [0u8; SNAPSHOT_FIELD_LEN];
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    Operation::Write(&[regs::STATUS]),
+                    Operation::Read(&mut live),
+                    Operation::Write(&[regs::AGC]),
+                    Operation::Read(&mut field),
+                ],
+            )
+            .await
+            .map_err(AS56Error::bus(self.address))?;
+        check_status(live[0])?;
+        Ok(decode_snapshot(live, field))
+    }
+
+    async fn get_config(&mut self) -> Result<Configuration, AS56Error<Self::Error>> {
+        let hi = self.read_u8(regs::CONF_HI).await?;
+        let lo = self.read_u8(regs::CONF_LO).await?;
+        Ok(Configuration::from_registers(hi, lo)?)
+    }
+
+    async fn set_config(&mut self, config: Configuration) -> Result<(), AS56Error<Self::Error>> {
+        let (hi, lo) = config.to_registers();
+        self.i2c
+            .write(self.address, &[regs::CONF_HI, hi, lo])
+            .await
+            .map_err(AS56Error::bus(self.address))
+    }
+
+    async fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>> {
+        if let Some(buf) = modify_conf(self.read_conf_raw().await?, f)? {
+            self.i2c
+                .write(self.address, &buf)
+                .await
+                .map_err(AS56Error::bus(self.address))?;
+        }
+        Ok(())
+    }
+
+    async fn get_zero_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::ZPOS_HI).await
+    }
+
+    async fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.write_u16(regs::ZPOS_HI, check_12bit(angle)?).await
+    }
+
+    async fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::MPOS_HI).await
+    }
+
+    async fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.write_u16(regs::MPOS_HI, check_12bit(angle)?).await
+    }
+
+    async fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.read_u16(regs::MANG_HI).await
+    }
+
+    async fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.write_u16(regs::MANG_HI, check_12bit(angle)?).await
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::AS5600Driver;
+    use crate::mock::{AS56Mock, MockError};
+    use crate::traits::AS5600Interface;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    /// Runs a future that never waits on anything but the mock.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Exposes the blocking mock as an `embedded-hal-async` bus.
+    struct AsyncMock(AS56Mock);
+
+    impl embedded_hal_async::i2c::ErrorType for AsyncMock {
+        type Error = MockError;
+    }
+
+    impl I2c for AsyncMock {
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            embedded_hal::i2c::I2c::transaction(&mut self.0, address, operations)
+        }
+    }
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    impl embedded_hal::delay::DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// Creates a blocking and an async driver, each on its own mock set up by `init`.
+    fn pair(
+        init: impl Fn(&AS56Mock),
+    ) -> (
+        (AS56Mock, AS5600Driver<AS56Mock>),
+        (AS56Mock, AS5600DriverAsync<AsyncMock>),
+    ) {
+        let (blocking, nonblocking) = (AS56Mock::new(), AS56Mock::new());
+        init(&blocking);
+        init(&nonblocking);
+        (
+            (blocking.clone(), AS5600Driver::new(blocking)),
+            (
+                nonblocking.clone(),
+                AS5600DriverAsync::new(AsyncMock(nonblocking)),
+            ),
+        )
+    }
+
+    #[test]
+    fn snapshot_matches_the_blocking_driver() {
+        let ((mock, mut sensor), (async_mock, mut async_sensor)) = pair(|mock| {
+            mock.mock_set_raw_angle(1234);
+            mock.mock_set_magnitude(0x456);
+        });
+        let snapshot = block_on(async_sensor.read_snapshot()).unwrap();
+        assert_eq!(snapshot.raw_angle, 1234);
+        assert_eq!(snapshot.magnitude, 0x456);
+        assert_eq!(snapshot, sensor.read_snapshot().unwrap());
+        async_mock.mock_assert_reads(regs::RAW_ANGLE_HI, 1);
+        assert_eq!(async_mock.mock_trace(), mock.mock_trace());
+    }
+
+    #[test]
+    fn set_config_is_one_write() {
+        let ((mock, mut sensor), (async_mock, mut async_sensor)) = pair(|_| {});
+        let config = Configuration {
+            power_mode: PowerMode::LPM2,
+            slow_filter: SlowFilter::X4,
+            ..Configuration::default()
+        };
+        let (hi, lo) = config.to_registers();
+        block_on(async_sensor.set_config(config)).unwrap();
+        sensor.set_config(config).unwrap();
+        async_mock.mock_assert_write(regs::CONF_HI, &[hi, lo]);
+        assert_eq!(async_mock.mock_log().len(), 1);
+        assert_eq!(async_mock.mock_trace(), mock.mock_trace());
+        assert_eq!(block_on(async_sensor.get_config()).unwrap(), config);
+    }
+
+    #[test]
+    fn modify_config_writes_only_changes() {
+        let ((mock, mut sensor), (async_mock, mut async_sensor)) = pair(|_| {});
+        for mode in [PowerMode::LPM1, PowerMode::LPM1] {
+            block_on(async_sensor.modify_config(|c| c.power_mode = mode)).unwrap();
+            sensor.modify_config(|c| c.power_mode = mode).unwrap();
+        }
+        let writes = async_mock
+            .mock_log()
+            .iter()
+            .filter(|entry| {
+                entry.direction == crate::mock::Direction::Write && entry.covers(regs::CONF_HI)
+            })
+            .count();
+        assert_eq!(writes, 1);
+        assert_eq!(async_mock.mock_trace(), mock.mock_trace());
+        let config = block_on(async_sensor.get_config()).unwrap();
+        assert_eq!(config.power_mode, PowerMode::LPM1);
+        assert!(config.watchdog);
+    }
+
+    #[test]
+    fn verified_burn_matches_the_blocking_driver() {
+        let ((mock, mut sensor), (async_mock, mut async_sensor)) = pair(|_| {});
+        let err = unsafe { block_on(async_sensor.danger_verified_burn_settings(&mut NoDelay)) };
+        assert_eq!(err, Err(AS56Error::NothingToBurn));
+        async_mock.mock_assert_no_write(regs::BURN);
+
+        block_on(async_sensor.set_zero_position(100)).unwrap();
+        block_on(async_sensor.set_max_position(3000)).unwrap();
+        unsafe { block_on(async_sensor.danger_verified_burn_settings(&mut NoDelay)) }.unwrap();
+        let otp = async_mock.mock_otp();
+        assert_eq!((otp.zpos, otp.mpos, otp.zmco), (100, 3000, 1));
+        assert_eq!(block_on(async_sensor.get_burn_count()).unwrap(), 1);
+
+        let _ = unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) };
+        sensor.set_zero_position(100).unwrap();
+        sensor.set_max_position(3000).unwrap();
+        unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+        sensor.get_burn_count().unwrap();
+        assert_eq!(async_mock.mock_trace(), mock.mock_trace());
+    }
+}
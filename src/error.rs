@@ -1,22 +1,265 @@
+use crate::types::InvalidConfig;
 use core::fmt;
+use embedded_hal::i2c::{NoAcknowledgeSource, SevenBitAddress};
 
 /// Custom error type for the AS5600 driver.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AS56Error<E> {
     /// Error from the underlying I2C communication.
     I2c(E),
+    /// No device acknowledged the address (sensor absent, unpowered or at another address).
+    ///
+    /// Contains the 7-bit address the driver talked to.
+    DeviceNotResponding(SevenBitAddress),
+    /// An argument does not fit the register it is written to. Nothing was written.
+    OutOfRange {
+        /// The rejected value.
+        value: u16,
+        /// The largest accepted value.
+        max: u16,
+    },
+    /// A register holds a value the chip cannot produce, e.g. after a corrupted transfer.
+    ImplausibleRegister {
+        /// The register that was read.
+        register: u8,
+        /// The value read from it.
+        value: u8,
+    },
+    /// A burn was refused because no magnet is detected (STATUS.MD is clear).
+    MagnetNotDetected,
+    /// A burn was refused because the OTP write budget is exhausted.
+    ///
+    /// Contains the ZMCO burn counter read from the chip.
+    BurnLimitReached(u8),
+    /// A burn was refused because it would not program anything: both ZPOS and MPOS
+    /// are zero (angle burn), or I2C_ADDR still holds the AS5600L factory default
+    /// (address burn).
+    NothingToBurn,
+    /// The requested I2C address is not a valid, non-reserved 7-bit address.
+    InvalidAddress(u8),
+    /// The address change was written, but the chip did not confirm it at the new address.
+    ///
+    /// Contains the new address, which the driver now targets.
+    AddressNotConfirmed(SevenBitAddress),
+    /// The value read back after an OTP burn differs from the one that was programmed.
+    OtpMismatch {
+        /// The HI register of the mismatching value.
+        register: u8,
+        /// The value that was programmed.
+        expected: u16,
+        /// The value loaded back from OTP.
+        actual: u16,
+    },
 }
 
 impl<E: fmt::Debug> fmt::Display for AS56Error<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AS56Error::I2c(e) => write!(f, "I2C error: {:?}", e),
+            AS56Error::DeviceNotResponding(addr) => {
+                write!(f, "no device responding at address 0x{:02X}", addr)
+            }
+            AS56Error::OutOfRange { value, max } => {
+                write!(f, "value {} out of range (max {})", value, max)
+            }
+            AS56Error::ImplausibleRegister { register, value } => write!(
+                f,
+                "implausible value 0x{:02X} in register 0x{:02X}",
+                value, register
+            ),
+            AS56Error::MagnetNotDetected => write!(f, "magnet not detected"),
+            AS56Error::BurnLimitReached(count) => {
+                write!(f, "OTP burn limit reached (ZMCO = {})", count)
+            }
+            AS56Error::NothingToBurn => write!(
+                f,
+                "nothing to burn (ZPOS and MPOS are both zero, or the address is the factory default)"
+            ),
+            AS56Error::InvalidAddress(addr) => write!(f, "invalid I2C address 0x{:02X}", addr),
+            AS56Error::AddressNotConfirmed(addr) => {
+                write!(
+                    f,
+                    "address change to 0x{:02X} written but not confirmed",
+                    addr
+                )
+            }
+            AS56Error::OtpMismatch {
+                register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "OTP verification failed at register 0x{:02X}: expected 0x{:04X}, read 0x{:04X}",
+                register, expected, actual
+            ),
         }
     }
 }
 
-#[cfg(feature = "std")]
-extern crate std;
+impl<E: fmt::Debug> core::error::Error for AS56Error<E> {}
 
-#[cfg(feature = "std")]
-impl<E: fmt::Debug> std::error::Error for AS56Error<E> {}
+impl<E> From<InvalidConfig> for AS56Error<E> {
+    fn from(e: InvalidConfig) -> Self {
+        AS56Error::ImplausibleRegister {
+            register: e.register,
+            value: e.value,
+        }
+    }
+}
+
+impl<E> AS56Error<E> {
+    /// Classifies the error, e.g. to decide whether the operation is worth retrying.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AS56Error::I2c(_) => ErrorKind::Bus,
+            AS56Error::DeviceNotResponding(_) | AS56Error::AddressNotConfirmed(_) => {
+                ErrorKind::NotResponding
+            }
+            AS56Error::ImplausibleRegister { .. } => ErrorKind::Corrupted,
+            AS56Error::OutOfRange { .. } | AS56Error::InvalidAddress(_) => ErrorKind::InvalidInput,
+            AS56Error::MagnetNotDetected | AS56Error::NothingToBurn => ErrorKind::Precondition,
+            AS56Error::BurnLimitReached(_) | AS56Error::OtpMismatch { .. } => ErrorKind::Permanent,
+        }
+    }
+}
+
+impl<E: embedded_hal::i2c::Error> AS56Error<E> {
+    /// Returns a mapper from bus errors of a transfer to `address`.
+    ///
+    /// A missing address acknowledge becomes [`AS56Error::DeviceNotResponding`].
+    pub(crate) fn bus(address: SevenBitAddress) -> impl FnOnce(E) -> Self {
+        move |e| match e.kind() {
+            embedded_hal::i2c::ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => {
+                AS56Error::DeviceNotResponding(address)
+            }
+            _ => AS56Error::I2c(e),
+        }
+    }
+}
+
+/// Broad classification of an [`AS56Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The I2C transfer failed. Usually transient.
+    Bus,
+    /// The sensor did not answer. Transient if it is being powered up or reconnected.
+    NotResponding,
+    /// The sensor returned impossible data. Reading again usually succeeds.
+    Corrupted,
+    /// The caller passed an invalid argument. Retrying with the same input fails again.
+    InvalidInput,
+    /// A precondition of the operation is not met (e.g. no magnet). Retry after fixing the setup.
+    Precondition,
+    /// The chip refuses the operation for good (OTP exhausted or mis-programmed).
+    Permanent,
+}
+
+impl ErrorKind {
+    /// Returns `true` if repeating the same operation may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Bus | ErrorKind::NotResponding | ErrorKind::Corrupted
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::ErrorKind as BusKind;
+
+    /// A bus error of a given kind.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct TestError(BusKind);
+
+    impl embedded_hal::i2c::Error for TestError {
+        fn kind(&self) -> BusKind {
+            self.0
+        }
+    }
+
+    #[test]
+    fn every_variant_has_a_kind() {
+        let cases: [(AS56Error<()>, ErrorKind); 11] = [
+            (AS56Error::I2c(()), ErrorKind::Bus),
+            (
+                AS56Error::DeviceNotResponding(0x36),
+                ErrorKind::NotResponding,
+            ),
+            (
+                AS56Error::AddressNotConfirmed(0x41),
+                ErrorKind::NotResponding,
+            ),
+            (
+                AS56Error::ImplausibleRegister {
+                    register: 0x07,
+                    value: 0xC0,
+                },
+                ErrorKind::Corrupted,
+            ),
+            (
+                AS56Error::OutOfRange {
+                    value: 4096,
+                    max: 4095,
+                },
+                ErrorKind::InvalidInput,
+            ),
+            (AS56Error::InvalidAddress(0x78), ErrorKind::InvalidInput),
+            (AS56Error::MagnetNotDetected, ErrorKind::Precondition),
+            (AS56Error::NothingToBurn, ErrorKind::Precondition),
+            (AS56Error::BurnLimitReached(3), ErrorKind::Permanent),
+            (
+                AS56Error::OtpMismatch {
+                    register: 0x01,
+                    expected: 100,
+                    actual: 0,
+                },
+                ErrorKind::Permanent,
+            ),
+            (
+                AS56Error::from(InvalidConfig {
+                    register: 0x08,
+                    value: 0xFF,
+                    mask: 0x30,
+                }),
+                ErrorKind::Corrupted,
+            ),
+        ];
+        for (error, kind) in cases {
+            assert_eq!(error.kind(), kind, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn only_communication_errors_are_transient() {
+        for (kind, transient) in [
+            (ErrorKind::Bus, true),
+            (ErrorKind::NotResponding, true),
+            (ErrorKind::Corrupted, true),
+            (ErrorKind::InvalidInput, false),
+            (ErrorKind::Precondition, false),
+            (ErrorKind::Permanent, false),
+        ] {
+            assert_eq!(kind.is_transient(), transient, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn address_nack_means_no_device() {
+        let map = |kind| AS56Error::bus(0x36)(TestError(kind));
+        assert_eq!(
+            map(BusKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            AS56Error::DeviceNotResponding(0x36)
+        );
+        for kind in [
+            BusKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            BusKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            BusKind::ArbitrationLoss,
+            BusKind::Bus,
+            BusKind::Other,
+        ] {
+            assert_eq!(map(kind), AS56Error::I2c(TestError(kind)));
+        }
+    }
+}
@@ -7,12 +7,22 @@
 //! contactless on-axis angular position measurement over a full turn of 360°.
 //!
 //! ## Features
-//! - Read raw and filtered angle (12-bit resolution)
+//! - Read raw and filtered angle (12-bit resolution), optionally as typed units
 //! - Configure power modes, hysteresis, and filters
 //! - Read magnet status (detected, too weak, too strong)
 //! - Automatic Gain Control (AGC) and Magnitude reading
 //! - Programming support (ZPOS, MPOS, MANG, and permanent BURN)
+//! - AS5600L variant with programmable I2C address
+//! - Multi-turn position tracking
+//! - Shadow register cache for the programming registers
+//! - Chip reset detection with re-application of volatile settings
+//! - Velocity and acceleration estimation (no_std)
+//! - TCA9548A/PCA9548 multiplexer support for several sensors on one bus
+//! - Terminal dashboard rendering through `core::fmt::Write` (no_std, no alloc)
 //! - Mock driver for testing and simulation
+//! - Recording and replay of I2C sessions (behind the `std` feature)
+//! - `as5600` command-line tool for Linux (behind the `cli` feature)
+//! - Async driver for `embedded-hal-async` executors (behind the `async` feature)
 //!
 //! ## Example (ESP32)
 //! ```rust,ignore
@@ -34,21 +44,56 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod cache;
+pub mod dashboard;
 pub mod driver;
 pub mod error;
+pub mod multiturn;
+pub mod mux;
 pub mod regs;
+pub mod supervisor;
 pub mod traits;
 pub mod types;
+pub mod units;
+pub mod variant;
+pub mod velocity;
+
+#[cfg(feature = "async")]
+pub mod driver_async;
 
 #[cfg(feature = "mock")]
 pub mod mock;
 
+#[cfg(feature = "std")]
+pub mod record;
+
+// Backs the `as5600` binary only; not part of the library API.
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+pub mod cli;
+
 // Re-exports for convenience
+pub use cache::Cached;
+pub use dashboard::Dashboard;
 pub use driver::AS5600Driver;
 pub use error::AS56Error;
+pub use multiturn::MultiTurn;
+pub use mux::Tca9548a;
 pub use regs::*;
+pub use supervisor::Supervisor;
 pub use traits::AS5600Interface;
 pub use types::*;
+pub use units::{Angle, RawCounts};
+pub use variant::{AS5600, AS5600L, Variant};
+pub use velocity::VelocityEstimator;
+
+#[cfg(feature = "async")]
+pub use driver_async::AS5600DriverAsync;
+#[cfg(feature = "async")]
+pub use traits::AS5600InterfaceAsync;
 
 #[cfg(feature = "mock")]
-pub use mock::AS56Mock;
+pub use mock::{AS56Mock, MockMux};
+
+#[cfg(feature = "std")]
+pub use record::{Recorder, Replay};
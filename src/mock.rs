@@ -1,23 +1,574 @@
+use crate::driver::{BURN_ANGLE_CMD, BURN_SETTING_CMD, OTP_RELOAD_SEQUENCE};
 use crate::regs::*;
 use crate::types::*;
+use core::time::Duration;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+use filter::AngleFilter;
+use magnet::MagnetSim;
+use power::PowerSim;
+use rng::Rng;
+use std::collections::VecDeque;
+use std::string::String;
 use std::sync::{Arc, Mutex};
+use std::vec::Vec;
+
+mod clock;
+mod fault;
+mod filter;
+mod log;
+mod magnet;
+mod mux;
+mod otp;
+mod power;
+mod rng;
+#[cfg(test)]
+mod tests;
+
+pub use clock::{RealtimeClock, SIM_STEP};
+pub use fault::{Fault, FaultAction, FaultTrigger};
+pub use filter::{fast_filter_threshold, hysteresis_lsb, slow_filter_settling};
+pub use log::{DEFAULT_LOG_CAPACITY, Direction, LogEntry, register_name};
+pub use magnet::{
+    FIELD_DETECT_MT, FIELD_MAX_MT, FIELD_MIN_MT, MagnetModel, NOMINAL_MAGNITUDE, SpeedProfile,
+};
+pub use mux::MockMux;
+pub use otp::{MAX_ANGLE_BURNS, MockOtp};
+pub use power::{WATCHDOG_THRESHOLD_LSB, WATCHDOG_TIMEOUT, polling_interval};
 
 /// Errors that can occur when using the mock driver.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MockError {
     /// Simulated I2C communication error.
     I2cError,
+    /// Error produced by a fault scheduled with the fault injection API.
+    Injected(ErrorKind),
+    /// A bus write targeted this read-only register while strict writes were enabled.
+    ReadOnly(u8),
+    /// No device acknowledged this 7-bit address.
+    NotAcknowledged(u8),
 }
 
 impl embedded_hal::i2c::Error for MockError {
     fn kind(&self) -> embedded_hal::i2c::ErrorKind {
-        embedded_hal::i2c::ErrorKind::Other
+        match self {
+            MockError::I2cError => ErrorKind::Other,
+            MockError::Injected(kind) => *kind,
+            // Reported like a data byte the device refused.
+            MockError::ReadOnly(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            MockError::NotAcknowledged(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        }
     }
 }
 
 /// Internal state shared between the mock I2C implementation and the controller.
 struct MockState {
+    /// Volatile register file. The programming registers are loaded from `otp`
+    /// at power-on, the output registers follow the simulated magnet.
     registers: [u8; 256],
+    /// 7-bit address the chip acknowledges.
+    address: u8,
+    /// Emulates an AS5600L, whose address is programmable through I2C_ADDR and I2C_UPDT.
+    as5600l: bool,
+    /// Register address pointer, kept between transactions like on the real chip.
+    pointer: u8,
+    /// Number of transactions executed so far.
+    transactions: u64,
+    /// Scheduled faults, evaluated in order on every transaction.
+    faults: Vec<Fault>,
+    /// Most recent I2C operations, oldest first.
+    log: VecDeque<LogEntry>,
+    /// Maximum number of entries kept in `log`.
+    log_capacity: usize,
+    /// Simulated OTP memory.
+    otp: MockOtp,
+    /// Progress through the OTP reload command sequence written to BURN.
+    reload_step: usize,
+    /// Fail bus writes to read-only registers instead of ignoring them.
+    strict_writes: bool,
+    /// Virtual time since the mock was created.
+    now: Duration,
+    /// Magnet simulation driving the output registers, if installed.
+    magnet: Option<MagnetSim>,
+    /// Filter and hysteresis emulation between RAW_ANGLE and ANGLE, if enabled.
+    filter: Option<AngleFilter>,
+    /// Power mode and watchdog emulation, if enabled.
+    power: Option<PowerSim>,
+}
+
+impl MockState {
+    /// Executes the operations of one I2C transaction against the register file.
+    ///
+    /// Scheduled faults are evaluated first: a failing fault aborts the transaction
+    /// before any register is touched, a corrupting one alters the returned bytes.
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), MockError> {
+        self.transactions += 1;
+        let (accesses, pointer) = plan_transaction(self.pointer, operations);
+
+        if address != self.address {
+            let error = MockError::NotAcknowledged(address);
+            let kind = embedded_hal::i2c::Error::kind(&error);
+            self.log_transaction(address, operations, &accesses, Some(kind));
+            return Err(error);
+        }
+
+        let mut corruption = Vec::new();
+        for fault in &mut self.faults {
+            let touched = accesses.iter().filter_map(|access| access.register());
+            if fault.fires(self.transactions, touched) {
+                match fault.action {
+                    FaultAction::Fail(kind) => {
+                        self.log_transaction(address, operations, &accesses, Some(kind));
+                        return Err(MockError::Injected(kind));
+                    }
+                    FaultAction::Corrupt(mask) => corruption.push((fault.register(), mask)),
+                }
+            }
+        }
+
+        if self.strict_writes {
+            let read_only = accesses.iter().find_map(|access| match *access {
+                ByteAccess::Write(reg) if is_read_only(reg) => Some(reg),
+                _ => None,
+            });
+            if let Some(reg) = read_only {
+                let error = MockError::ReadOnly(reg);
+                self.log_transaction(
+                    address,
+                    operations,
+                    &accesses,
+                    Some(embedded_hal::i2c::Error::kind(&error)),
+                );
+                return Err(error);
+            }
+        }
+
+        let mut accesses_iter = accesses.iter().copied();
+        let mut touches_range = false;
+        let mut burn_commands = Vec::new();
+        let mut new_address = None;
+        for op in operations.iter_mut() {
+            match op {
+                Operation::Write(bytes) => {
+                    for &val in bytes.iter() {
+                        if let Some(ByteAccess::Write(reg)) = accesses_iter.next() {
+                            // The chip acknowledges but ignores writes to read-only registers.
+                            if is_read_only(reg) {
+                                continue;
+                            }
+                            self.registers[reg as usize] = val;
+                            touches_range |= (regs::ZPOS_HI..=regs::MANG_LO).contains(&reg);
+                            if reg == regs::BURN {
+                                burn_commands.push(val);
+                            }
+                            if reg == regs::I2C_UPDT && self.as5600l {
+                                new_address = Some(val >> 1);
+                            }
+                        }
+                    }
+                }
+                Operation::Read(buf) => {
+                    for byte in buf.iter_mut() {
+                        if let Some(ByteAccess::Read(reg)) = accesses_iter.next() {
+                            let mut val = self.registers[reg as usize];
+                            for &(only, mask) in &corruption {
+                                if only.is_none_or(|r| r == reg) {
+                                    val ^= mask;
+                                }
+                            }
+                            *byte = val;
+                        }
+                    }
+                }
+            }
+        }
+        self.log_transaction(address, operations, &accesses, None);
+        self.pointer = pointer;
+        // I2C_UPDT is acknowledged at the old address, later transfers go to the new one.
+        if let Some(address) = new_address {
+            self.address = address;
+        }
+
+        for cmd in burn_commands {
+            touches_range |= self.burn_command(cmd);
+        }
+        // ZPOS, MPOS and MANG affect the scaled output immediately.
+        if touches_range {
+            self.update_angle();
+        }
+        Ok(())
+    }
+
+    /// Emulates a command written to the BURN register.
+    ///
+    /// Returns `true` if the programming registers were reloaded from OTP.
+    fn burn_command(&mut self, cmd: u8) -> bool {
+        match cmd {
+            BURN_ANGLE_CMD => {
+                let detected = self.registers[regs::STATUS as usize] & 0x20 != 0;
+                let (zpos, mpos) = (self.reg_u16(regs::ZPOS_HI), self.reg_u16(regs::MPOS_HI));
+                if self.otp.burn_angle(zpos, mpos, detected) {
+                    self.registers[regs::ZMCO as usize] = self.otp.zmco;
+                }
+            }
+            BURN_SETTING_CMD => {
+                let mang = self.reg_u16(regs::MANG_HI);
+                let conf = self.conf_word();
+                let i2c_addr = if self.as5600l {
+                    self.registers[regs::I2C_ADDR as usize]
+                } else {
+                    self.otp.i2c_addr
+                };
+                self.otp.burn_setting(mang, conf, i2c_addr);
+            }
+            _ => {}
+        }
+
+        // The reload sequence must be written in order, any other command restarts it.
+        if cmd == OTP_RELOAD_SEQUENCE[self.reload_step] {
+            self.reload_step += 1;
+        } else {
+            self.reload_step = usize::from(cmd == OTP_RELOAD_SEQUENCE[0]);
+        }
+        if self.reload_step == OTP_RELOAD_SEQUENCE.len() {
+            self.reload_step = 0;
+            self.load_otp();
+            return true;
+        }
+        false
+    }
+
+    /// Resets the chip to its power-on state.
+    ///
+    /// The output registers keep following the magnet, every other register is
+    /// cleared and the programming registers are loaded from OTP. An AS5600L
+    /// answers at the address burned to OTP again.
+    fn power_on(&mut self) {
+        for reg in 0..=u8::MAX {
+            if !is_read_only(reg) {
+                self.registers[reg as usize] = 0;
+            }
+        }
+        self.pointer = 0;
+        self.reload_step = 0;
+        let raw = self.reg_u16(regs::RAW_ANGLE_HI);
+        if self.filter.is_some() {
+            self.filter = Some(AngleFilter::new(raw));
+        }
+        if self.power.is_some() {
+            self.power = Some(PowerSim::new(self.now, raw));
+        }
+        self.load_otp();
+        self.address = if self.as5600l {
+            self.otp.i2c_addr >> 1
+        } else {
+            DEFAULT_ADDR
+        };
+        self.update_angle();
+    }
+
+    /// Advances the virtual clock by `dt`, moving the simulated shaft in steps of
+    /// at most [`SIM_STEP`].
+    fn advance(&mut self, dt: Duration) {
+        let end = self.now + dt;
+        while self.now < end {
+            let step = (end - self.now).min(SIM_STEP);
+            if let Some(sim) = &mut self.magnet {
+                sim.step(self.now, step);
+            }
+            self.now += step;
+
+            // With power mode emulation the chip only samples at its polling interval.
+            let config = self.config();
+            let elapsed = match &mut self.power {
+                Some(power) => match power.poll(self.now, config.power_mode) {
+                    Some(elapsed) => elapsed,
+                    None => continue,
+                },
+                None => step,
+            };
+            self.sense();
+            let raw = self.reg_u16(regs::RAW_ANGLE_HI);
+            if let Some(filter) = &mut self.filter {
+                filter.step(raw, &config, elapsed);
+            }
+            if let Some(power) = &mut self.power {
+                power.watchdog(raw, config.watchdog, self.now);
+            }
+        }
+        self.update_angle();
+    }
+
+    /// Decodes the current CONF registers.
+    ///
+    /// The output stage is not emulated, so OUTS and the unused bits are ignored
+    /// and a reserved pattern there cannot fail the decoding.
+    fn config(&self) -> Configuration {
+        let hi = self.registers[regs::CONF_HI as usize] & 0x3F;
+        let lo = self.registers[regs::CONF_LO as usize] & !0x30;
+        Configuration::from_registers(hi, lo).expect("no reserved pattern left")
+    }
+
+    /// Samples the input after it was changed through the controller API: at
+    /// once, or at the next poll if power mode emulation is enabled.
+    fn input_changed(&mut self) {
+        if self.power.is_none() {
+            self.sense();
+        }
+    }
+
+    /// Samples the magnet model (or a raw angle set by hand) into the output registers.
+    fn sense(&mut self) {
+        if let Some(raw) = self
+            .power
+            .as_mut()
+            .and_then(|power| power.pending_raw.take())
+        {
+            self.set_reg_u16(regs::RAW_ANGLE_HI, raw);
+            self.update_angle();
+        }
+        let Some(sim) = &mut self.magnet else {
+            return;
+        };
+        let model = sim.model;
+        let raw = model.raw_angle(sim.shaft_deg, &mut sim.rng);
+        self.registers[regs::STATUS as usize] = model.status();
+        self.registers[regs::AGC as usize] = model.agc();
+        self.set_reg_u16(regs::MAGNITUDE_HI, model.magnitude());
+        self.set_reg_u16(regs::RAW_ANGLE_HI, raw);
+        self.update_angle();
+    }
+
+    /// Returns the magnet simulation, installing the default model at the
+    /// current RAW_ANGLE if there is none.
+    fn magnet_sim(&mut self) -> &mut MagnetSim {
+        let shaft_deg = self.reg_u16(regs::RAW_ANGLE_HI) as f64 * 360.0 / 4096.0;
+        self.magnet
+            .get_or_insert_with(|| MagnetSim::new(MagnetModel::default(), shaft_deg))
+    }
+
+    /// Copies the OTP content into the ZPOS, MPOS, MANG and CONF registers, and
+    /// into I2C_ADDR and I2C_UPDT on an AS5600L.
+    fn load_otp(&mut self) {
+        let otp = self.otp;
+        self.set_reg_u16(regs::ZPOS_HI, otp.zpos);
+        self.set_reg_u16(regs::MPOS_HI, otp.mpos);
+        self.set_reg_u16(regs::MANG_HI, otp.mang);
+        let conf = otp.conf.to_be_bytes();
+        self.registers[regs::CONF_HI as usize] = conf[0];
+        self.registers[regs::CONF_LO as usize] = conf[1];
+        self.registers[regs::ZMCO as usize] = otp.zmco;
+        if self.as5600l {
+            self.registers[regs::I2C_ADDR as usize] = otp.i2c_addr;
+            self.registers[regs::I2C_UPDT as usize] = otp.i2c_addr;
+        }
+    }
+
+    /// Returns the 14 significant bits of CONF as one word.
+    fn conf_word(&self) -> u16 {
+        let hi = self.registers[regs::CONF_HI as usize] & 0x3F;
+        u16::from_be_bytes([hi, self.registers[regs::CONF_LO as usize]])
+    }
+
+    /// Appends one log entry per operation, dropping the oldest entries beyond capacity.
+    fn log_transaction(
+        &mut self,
+        address: u8,
+        operations: &[Operation<'_>],
+        accesses: &[ByteAccess],
+        error: Option<ErrorKind>,
+    ) {
+        let mut pos = 0;
+        for op in operations {
+            let (direction, data): (_, &[u8]) = match op {
+                Operation::Write(bytes) => (Direction::Write, bytes),
+                Operation::Read(buf) => (Direction::Read, buf),
+            };
+            let op_accesses = &accesses[pos..pos + data.len()];
+            pos += data.len();
+
+            let (register, bytes) = match op_accesses.first() {
+                Some(ByteAccess::Pointer(reg)) => (*reg, &data[1..]),
+                Some(ByteAccess::Write(reg) | ByteAccess::Read(reg)) => (*reg, data),
+                None => (self.pointer, data),
+            };
+            // A failed read returns no data.
+            let bytes = match (direction, error) {
+                (Direction::Read, Some(_)) => Vec::new(),
+                _ => bytes.to_vec(),
+            };
+
+            if self.log.len() >= self.log_capacity {
+                self.log.pop_front();
+            }
+            if self.log_capacity > 0 {
+                self.log.push_back(LogEntry {
+                    seq: self.transactions,
+                    address,
+                    direction,
+                    register,
+                    bytes,
+                    error,
+                });
+            }
+        }
+    }
+
+    /// Reads a 12-bit value from a HI/LO register pair.
+    fn reg_u16(&self, reg_hi: u8) -> u16 {
+        let hi = self.registers[reg_hi as usize];
+        let lo = self.registers[reg_hi as usize + 1];
+        u16::from_be_bytes([hi, lo]) & 0x0FFF
+    }
+
+    /// Writes a 12-bit value to a HI/LO register pair.
+    fn set_reg_u16(&mut self, reg_hi: u8, value: u16) {
+        let bytes = (value & 0x0FFF).to_be_bytes();
+        self.registers[reg_hi as usize] = bytes[0];
+        self.registers[reg_hi as usize + 1] = bytes[1];
+    }
+
+    /// Recomputes the ANGLE output from RAW_ANGLE (through the filter emulation,
+    /// if enabled) and the ZPOS/MPOS/MANG settings.
+    fn update_angle(&mut self) {
+        let raw = match &self.filter {
+            Some(filter) => filter.output(),
+            None => self.reg_u16(regs::RAW_ANGLE_HI),
+        };
+        let angle = scale_angle(
+            raw,
+            self.reg_u16(regs::ZPOS_HI),
+            self.reg_u16(regs::MPOS_HI),
+            self.reg_u16(regs::MANG_HI),
+        );
+        self.set_reg_u16(regs::ANGLE_HI, angle);
+    }
+}
+
+/// Returns `true` for registers the bus side cannot write.
+fn is_read_only(reg: u8) -> bool {
+    matches!(
+        reg,
+        regs::ZMCO | regs::STATUS..=regs::ANGLE_LO | regs::AGC..=regs::MAGNITUDE_LO
+    )
+}
+
+/// What one byte of a transaction does to the register file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteAccess {
+    /// Address byte that moves the register pointer.
+    Pointer(u8),
+    /// Data byte stored in a register.
+    Write(u8),
+    /// Data byte returned from a register.
+    Read(u8),
+}
+
+impl ByteAccess {
+    /// Returns the register whose content is read or written.
+    fn register(&self) -> Option<u8> {
+        match *self {
+            ByteAccess::Pointer(_) => None,
+            ByteAccess::Write(reg) | ByteAccess::Read(reg) => Some(reg),
+        }
+    }
+}
+
+/// Maps every byte of `operations` to the register it accesses.
+///
+/// The first byte of a write sets the address pointer, further bytes are stored
+/// at the pointer with auto-increment. A write directly following another write
+/// continues the same write (no new address byte), as `embedded-hal` specifies.
+/// Reads start at the pointer and auto-increment as well.
+///
+/// RAW_ANGLE, ANGLE and MAGNITUDE are special: when a read starts at their HI
+/// byte, the pointer does not advance past the LO byte but returns to the HI
+/// byte, so repeated plain reads keep returning the fresh 12-bit value without
+/// re-sending the address. A burst that runs into them from an earlier register
+/// (e.g. STATUS..ANGLE_LO) auto-increments normally.
+///
+/// Returns the accesses in bus order and the pointer after the transaction.
+fn plan_transaction(mut pointer: u8, operations: &[Operation<'_>]) -> (Vec<ByteAccess>, u8) {
+    let mut accesses = Vec::new();
+    let mut prev_was_write = false;
+    for op in operations {
+        match op {
+            Operation::Write(bytes) => {
+                for (i, &byte) in bytes.iter().enumerate() {
+                    if i == 0 && !prev_was_write {
+                        pointer = byte;
+                        accesses.push(ByteAccess::Pointer(byte));
+                    } else {
+                        accesses.push(ByteAccess::Write(pointer));
+                        pointer = pointer.wrapping_add(1);
+                    }
+                }
+                prev_was_write = true;
+            }
+            Operation::Read(buf) => {
+                let start = pointer;
+                for _ in 0..buf.len() {
+                    accesses.push(ByteAccess::Read(pointer));
+                    pointer = if is_wrapping_lo(pointer) && start == pointer - 1 {
+                        start
+                    } else {
+                        pointer.wrapping_add(1)
+                    };
+                }
+                prev_was_write = false;
+            }
+        }
+    }
+    (accesses, pointer)
+}
+
+/// Returns `true` for the LO bytes whose reads return the pointer to the HI byte.
+fn is_wrapping_lo(reg: u8) -> bool {
+    matches!(
+        reg,
+        regs::RAW_ANGLE_LO | regs::ANGLE_LO | regs::MAGNITUDE_LO
+    )
+}
+
+/// Computes the scaled ANGLE output the way the AS5600 does.
+///
+/// The output range starts at ZPOS and spans either up to MPOS (wrapping through
+/// 0 when ZPOS > MPOS) or, if MPOS is not set, MANG counts. With neither set the
+/// full turn is used. Inside the range the 12-bit output is stretched over the
+/// range; outside it the output is clamped to the nearest end point.
+fn scale_angle(raw: u16, zpos: u16, mpos: u16, mang: u16) -> u16 {
+    const FULL_TURN: u32 = 4096;
+
+    let offset = (raw as u32 + FULL_TURN - zpos as u32) % FULL_TURN;
+    let range = if mpos != 0 {
+        match (mpos as u32 + FULL_TURN - zpos as u32) % FULL_TURN {
+            0 => FULL_TURN,
+            r => r,
+        }
+    } else if mang != 0 {
+        mang as u32
+    } else {
+        FULL_TURN
+    };
+
+    if range == FULL_TURN {
+        return offset as u16;
+    }
+    if offset <= range {
+        return (offset * FULL_TURN / range).min(FULL_TURN - 1) as u16;
+    }
+
+    // Outside the programmed range: hold the closest end point.
+    let past_end = offset - range;
+    let gap = FULL_TURN - range;
+    if past_end <= gap / 2 {
+        (FULL_TURN - 1) as u16
+    } else {
+        0
+    }
 }
 
 /// A mock I2C device that emulates AS5600 behavior.
@@ -37,26 +588,80 @@ impl AS56Mock {
     /// - Magnet detected
     /// - AGC at 100
     /// - Watchdog enabled
+    ///
+    /// The chip starts with blank OTP (apart from the watchdog bit), so ZPOS, MPOS
+    /// and MANG power on as zero. It acknowledges only the AS5600 address (0x36);
+    /// transactions to any other address fail like a missing ACK.
     pub fn new() -> Self {
+        Self::power_up(false)
+    }
+
+    /// Creates a mock AS5600L at its factory default address (0x40).
+    ///
+    /// Writing I2C_UPDT moves the chip to the address in bits 7:1 until the next
+    /// power cycle; the setting burn stores I2C_ADDR in OTP, so the chip powers up
+    /// at that address afterwards.
+    pub fn new_as5600l() -> Self {
+        Self::power_up(true)
+    }
+
+    fn power_up(as5600l: bool) -> Self {
         let mut registers = [0u8; 256];
         // Default healthy state
         registers[regs::STATUS as usize] = 0x20; // Detected
         registers[regs::AGC as usize] = 100;
-        registers[regs::CONF_HI as usize] = 0x20; // Watchdog ON
+
+        let mut state = MockState {
+            registers,
+            address: DEFAULT_ADDR,
+            as5600l,
+            pointer: 0,
+            transactions: 0,
+            faults: Vec::new(),
+            log: VecDeque::new(),
+            log_capacity: DEFAULT_LOG_CAPACITY,
+            otp: MockOtp {
+                conf: 0x2000, // Watchdog ON
+                i2c_addr: if as5600l {
+                    AS5600L_DEFAULT_ADDR << 1
+                } else {
+                    0
+                },
+                ..MockOtp::default()
+            },
+            reload_step: 0,
+            strict_writes: false,
+            now: Duration::ZERO,
+            magnet: None,
+            filter: None,
+            power: None,
+        };
+        state.power_on();
 
         Self {
-            state: Arc::new(Mutex::new(MockState { registers })),
+            state: Arc::new(Mutex::new(state)),
         }
     }
 
     // --- Simulation Controller API ---
 
     /// Sets the raw angle that the mock will report.
+    ///
+    /// The scaled ANGLE output is recomputed from ZPOS, MPOS and MANG. With a
+    /// magnet model installed this turns the simulated shaft to that angle instead.
+    /// With power mode emulation enabled the new angle is sampled at the next poll.
     pub fn mock_set_raw_angle(&self, angle: u16) {
         let mut state = self.state.lock().unwrap();
-        let bytes = (angle & 0x0FFF).to_be_bytes();
-        state.registers[regs::RAW_ANGLE_HI as usize] = bytes[0];
-        state.registers[regs::RAW_ANGLE_LO as usize] = bytes[1];
+        if let Some(sim) = &mut state.magnet {
+            sim.shaft_deg = (angle & 0x0FFF) as f64 * 360.0 / 4096.0;
+        } else if let Some(power) = &mut state.power {
+            power.pending_raw = Some(angle & 0x0FFF);
+            return;
+        } else {
+            state.set_reg_u16(regs::RAW_ANGLE_HI, angle);
+        }
+        state.input_changed();
+        state.update_angle();
     }
 
     /// Sets the magnet status that the mock will report.
@@ -90,51 +695,332 @@ impl AS56Mock {
     }
 }
 
+impl AS56Mock {
+    // --- Fault Injection API ---
+
+    /// Schedules a fault. Faults fire in the order they were added.
+    pub fn mock_inject_fault(&self, trigger: FaultTrigger, action: FaultAction) {
+        let mut state = self.state.lock().unwrap();
+        state.faults.push(Fault::new(trigger, action));
+    }
+
+    /// Makes the `n`-th transaction from now fire `action` (1 = the next transaction).
+    pub fn mock_fail_nth(&self, n: u64, action: FaultAction) {
+        let seq = self.mock_transaction_count() + n.max(1);
+        self.mock_inject_fault(FaultTrigger::Transaction(seq), action);
+    }
+
+    /// Makes every transaction fire `action` with `probability`, using an RNG seeded with `seed`.
+    pub fn mock_fail_random(&self, probability: f32, seed: u64, action: FaultAction) {
+        self.mock_inject_fault(FaultTrigger::Random { probability, seed }, action);
+    }
+
+    /// Makes every transaction that reads or writes `reg` fire `action`.
+    pub fn mock_fail_register(&self, reg: u8, action: FaultAction) {
+        self.mock_inject_fault(FaultTrigger::Register(reg), action);
+    }
+
+    /// Returns the scheduled faults with their hit counters.
+    pub fn mock_faults(&self) -> Vec<Fault> {
+        self.state.lock().unwrap().faults.clone()
+    }
+
+    /// Removes all scheduled faults.
+    pub fn mock_clear_faults(&self) {
+        self.state.lock().unwrap().faults.clear();
+    }
+
+    /// Returns how many I2C transactions the mock has executed (including failed ones).
+    pub fn mock_transaction_count(&self) -> u64 {
+        self.state.lock().unwrap().transactions
+    }
+}
+
+impl AS56Mock {
+    // --- Transaction Log API ---
+
+    /// Returns the logged I2C operations, oldest first.
+    pub fn mock_log(&self) -> Vec<LogEntry> {
+        self.state.lock().unwrap().log.iter().cloned().collect()
+    }
+
+    /// Clears the transaction log.
+    pub fn mock_clear_log(&self) {
+        self.state.lock().unwrap().log.clear();
+    }
+
+    /// Sets how many operations the log keeps (default [`DEFAULT_LOG_CAPACITY`]).
+    ///
+    /// A capacity of 0 disables logging.
+    pub fn mock_set_log_capacity(&self, capacity: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.log_capacity = capacity;
+        while state.log.len() > capacity {
+            state.log.pop_front();
+        }
+    }
+
+    /// Returns the log as a register-level trace, one operation per line.
+    pub fn mock_trace(&self) -> String {
+        let mut trace = String::new();
+        for entry in self.state.lock().unwrap().log.iter() {
+            trace.push_str(&std::format!("{}\n", entry));
+        }
+        trace
+    }
+
+    /// Asserts that `bytes` were written to consecutive registers starting at `reg`,
+    /// in one write operation.
+    ///
+    /// Panics with the trace if no logged write matches.
+    pub fn mock_assert_write(&self, reg: u8, bytes: &[u8]) {
+        let found = self.mock_log().iter().any(|entry| {
+            entry.direction == Direction::Write
+                && entry.error.is_none()
+                && bytes
+                    .iter()
+                    .enumerate()
+                    .all(|(i, &b)| entry.byte_for(reg.wrapping_add(i as u8)) == Some(b))
+        });
+        if !found {
+            panic!(
+                "expected write {} = {:02X?}, trace:\n{}",
+                register_name(reg),
+                bytes,
+                self.mock_trace()
+            );
+        }
+    }
+
+    /// Asserts that no logged write touched `reg`.
+    ///
+    /// Panics with the trace otherwise.
+    pub fn mock_assert_no_write(&self, reg: u8) {
+        let written = self
+            .mock_log()
+            .iter()
+            .any(|entry| entry.direction == Direction::Write && entry.covers(reg));
+        if written {
+            panic!(
+                "expected no write to {}, trace:\n{}",
+                register_name(reg),
+                self.mock_trace()
+            );
+        }
+    }
+
+    /// Asserts that exactly `count` logged read operations returned data of `reg`.
+    ///
+    /// Panics with the trace otherwise.
+    pub fn mock_assert_reads(&self, reg: u8, count: usize) {
+        let reads = self
+            .mock_log()
+            .iter()
+            .filter(|entry| entry.direction == Direction::Read && entry.covers(reg))
+            .count();
+        if reads != count {
+            panic!(
+                "expected {} read(s) of {}, got {}, trace:\n{}",
+                count,
+                register_name(reg),
+                reads,
+                self.mock_trace()
+            );
+        }
+    }
+}
+
+impl AS56Mock {
+    // --- OTP Emulation API ---
+
+    /// Returns the simulated OTP memory (burned values, ZMCO and refused burns).
+    pub fn mock_otp(&self) -> MockOtp {
+        self.state.lock().unwrap().otp
+    }
+
+    /// Returns the 7-bit address the chip currently acknowledges.
+    pub fn mock_address(&self) -> u8 {
+        self.state.lock().unwrap().address
+    }
+
+    /// Simulates a power cycle.
+    ///
+    /// Everything written over the bus is lost: the programming registers are
+    /// reloaded from OTP and the register pointer is reset. The output registers
+    /// (STATUS, RAW_ANGLE, AGC, MAGNITUDE) keep reflecting the magnet.
+    pub fn mock_power_cycle(&self) {
+        self.state.lock().unwrap().power_on();
+    }
+
+    /// Makes bus writes to read-only registers fail with [`MockError::ReadOnly`].
+    ///
+    /// By default such writes are silently ignored, like on the real chip.
+    pub fn mock_set_strict_writes(&self, strict: bool) {
+        self.state.lock().unwrap().strict_writes = strict;
+    }
+}
+
+impl AS56Mock {
+    // --- Magnet Simulation API ---
+
+    /// Installs a physical magnet model that drives RAW_ANGLE, ANGLE, MAGNITUDE,
+    /// AGC and STATUS from now on.
+    ///
+    /// The shaft keeps its position and speed profile if a model was already
+    /// installed, otherwise it starts at the current RAW_ANGLE, standing still.
+    /// While a model is installed, values set with `mock_set_status`,
+    /// `mock_set_agc` and `mock_set_magnitude` are overwritten on the next sample.
+    pub fn mock_set_magnet(&self, model: MagnetModel) {
+        let mut state = self.state.lock().unwrap();
+        let sim = state.magnet_sim();
+        sim.model = model;
+        sim.rng = Rng::new(model.seed);
+        state.input_changed();
+    }
+
+    /// Removes the magnet model; the output registers keep their last values
+    /// and can be set by hand again.
+    pub fn mock_clear_magnet(&self) {
+        self.state.lock().unwrap().magnet = None;
+    }
+
+    /// Returns the installed magnet model, if any.
+    pub fn mock_magnet(&self) -> Option<MagnetModel> {
+        self.state
+            .lock()
+            .unwrap()
+            .magnet
+            .as_ref()
+            .map(|sim| sim.model)
+    }
+
+    /// Turns the simulated shaft to `degrees`, installing the default magnet
+    /// model if none is set.
+    pub fn mock_set_shaft_angle(&self, degrees: f32) {
+        let mut state = self.state.lock().unwrap();
+        state.magnet_sim().shaft_deg = (degrees as f64).rem_euclid(360.0);
+        state.input_changed();
+    }
+
+    /// Returns the simulated shaft position in degrees (0..360), if a magnet
+    /// model is installed.
+    pub fn mock_shaft_angle(&self) -> Option<f32> {
+        let state = self.state.lock().unwrap();
+        state.magnet.as_ref().map(|sim| sim.shaft_deg as f32)
+    }
+
+    /// Sets how the shaft moves as the virtual clock advances, installing the
+    /// default magnet model if none is set. The profile's time starts now.
+    pub fn mock_set_speed_profile(&self, profile: SpeedProfile) {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        let sim = state.magnet_sim();
+        sim.profile = profile;
+        sim.profile_start = now;
+    }
+
+    /// Advances the virtual clock by `dt`, moving the shaft along its speed
+    /// profile and sampling the outputs.
+    pub fn mock_advance(&self, dt: Duration) {
+        self.state.lock().unwrap().advance(dt);
+    }
+
+    /// Enables or disables emulation of the slow filter, fast filter threshold and
+    /// hysteresis configured in CONF.
+    ///
+    /// When enabled, ANGLE no longer follows RAW_ANGLE immediately: it settles
+    /// toward it as the virtual clock advances with [`mock_advance`](Self::mock_advance).
+    /// Disabled by default, so ANGLE tracks `mock_set_raw_angle` at once.
+    pub fn mock_set_filter_emulation(&self, enabled: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.filter = enabled.then(|| AngleFilter::new(state.reg_u16(regs::RAW_ANGLE_HI)));
+        state.update_angle();
+    }
+
+    /// Enables or disables emulation of the power modes and the watchdog
+    /// configured in CONF.
+    ///
+    /// When enabled, the outputs are only updated at the polling interval of the
+    /// active power mode (see [`polling_interval`]) as the virtual clock advances,
+    /// and with the watchdog bit set the chip drops into LPM3 after
+    /// [`WATCHDOG_TIMEOUT`] within [`WATCHDOG_THRESHOLD_LSB`] and wakes on movement.
+    /// Disabled by default, so the outputs follow the controller API at once.
+    pub fn mock_set_power_emulation(&self, enabled: bool) {
+        let mut state = self.state.lock().unwrap();
+        let (now, raw) = (state.now, state.reg_u16(regs::RAW_ANGLE_HI));
+        state.power = enabled.then(|| PowerSim::new(now, raw));
+    }
+
+    /// Returns the power mode the emulated chip currently runs in, taking a
+    /// watchdog-forced LPM3 into account.
+    ///
+    /// Without power mode emulation this is the mode configured in CONF.
+    pub fn mock_power_mode(&self) -> PowerMode {
+        let state = self.state.lock().unwrap();
+        let configured = state.config().power_mode;
+        match &state.power {
+            Some(power) => power.effective_mode(configured),
+            None => configured,
+        }
+    }
+
+    /// Returns `true` if the watchdog has put the emulated chip into LPM3.
+    pub fn mock_watchdog_sleeping(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.power.is_some_and(|power| power.sleeping)
+    }
+
+    /// Returns the virtual time elapsed since the mock was created.
+    pub fn mock_now(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    /// Starts a background thread that advances the virtual clock with wall
+    /// time, waking up every `tick`.
+    ///
+    /// The thread runs until the returned handle is dropped.
+    pub fn mock_run_realtime(&self, tick: Duration) -> RealtimeClock {
+        RealtimeClock::start(self.clone(), tick)
+    }
+}
+
+impl Default for AS56Mock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl embedded_hal::i2c::ErrorType for AS56Mock {
     type Error = MockError;
 }
 
 impl embedded_hal::i2c::I2c<embedded_hal::i2c::SevenBitAddress> for AS56Mock {
-    fn read(&mut self, _address: u8, _read: &mut [u8]) -> Result<(), Self::Error> {
-        // Simple read from the last register is not fully implemented in this mock
-        // as the AS5600 driver always uses write_read for register access.
-        Ok(())
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.transaction(address, &mut [Operation::Read(read)])
     }
 
-    fn write(&mut self, _address: u8, write: &[u8]) -> Result<(), Self::Error> {
-        let mut state = self.state.lock().unwrap();
-        if write.len() >= 2 {
-            let reg = write[0] as usize;
-            for (i, val) in write.iter().skip(1).enumerate() {
-                if reg + i < 256 {
-                    state.registers[reg + i] = *val;
-                }
-            }
-        }
-        Ok(())
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.transaction(address, &mut [Operation::Write(write)])
     }
 
     fn write_read(
         &mut self,
-        _address: u8,
+        address: u8,
         write: &[u8],
         read: &mut [u8],
     ) -> Result<(), Self::Error> {
-        let state = self.state.lock().unwrap();
-        let reg = write[0] as usize;
-        for i in 0..read.len() {
-            if reg + i < 256 {
-                read[i] = state.registers[reg + i];
-            }
-        }
-        Ok(())
+        self.transaction(
+            address,
+            &mut [Operation::Write(write), Operation::Read(read)],
+        )
     }
 
     fn transaction(
         &mut self,
-        _address: u8,
-        _operations: &mut [embedded_hal::i2c::Operation<'_>],
+        address: u8,
+        operations: &mut [Operation<'_>],
     ) -> Result<(), Self::Error> {
-        unimplemented!("Full I2C transactions are not implemented in this mock")
+        let mut state = self.state.lock().unwrap();
+        state.transaction(address, operations)
     }
 }
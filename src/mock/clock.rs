@@ -0,0 +1,57 @@
+use super::AS56Mock;
+use core::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+
+/// Resolution of the simulation: the virtual clock advances in steps of at most
+/// this long, the sampling period of the chip.
+pub const SIM_STEP: Duration = Duration::from_micros(150);
+
+/// Background thread that advances the virtual clock of an [`AS56Mock`] in real time.
+///
+/// Created by [`AS56Mock::mock_run_realtime`]. The thread stops when the handle is
+/// dropped or [`stop`](Self::stop) is called.
+pub struct RealtimeClock {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RealtimeClock {
+    pub(super) fn start(mock: AS56Mock, tick: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        let thread = thread::spawn(move || {
+            let mut last = Instant::now();
+            while !flag.load(Ordering::Relaxed) {
+                thread::sleep(tick);
+                let now = Instant::now();
+                mock.mock_advance(now - last);
+                last = now;
+            }
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Stops the thread and waits for it to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RealtimeClock {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
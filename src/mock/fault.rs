@@ -0,0 +1,93 @@
+use super::rng::Rng;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+/// When an injected fault fires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FaultTrigger {
+    /// Fires once, on the transaction with this sequence number (the first
+    /// transaction of the mock is number 1).
+    Transaction(u64),
+    /// Fires on each transaction with the given probability (`0.0..=1.0`),
+    /// drawn from a deterministic RNG seeded with `seed`.
+    Random {
+        /// Probability of firing per transaction.
+        probability: f32,
+        /// Seed of the fault's private RNG, for reproducible test runs.
+        seed: u64,
+    },
+    /// Fires on every transaction that reads or writes this register.
+    Register(u8),
+}
+
+/// What an injected fault does when it fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultAction {
+    /// Aborts the transaction with this error kind; no register is touched.
+    Fail(ErrorKind),
+    /// Completes the transaction but XORs the returned bytes with this mask.
+    ///
+    /// With a [`FaultTrigger::Register`] trigger only the bytes of that register
+    /// are corrupted, otherwise every byte read in the transaction is.
+    Corrupt(u8),
+}
+
+impl FaultAction {
+    /// Missing ACK of the device address (device absent or busy).
+    pub const NACK_ADDRESS: Self =
+        Self::Fail(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+    /// Missing ACK of a data byte.
+    pub const NACK_DATA: Self = Self::Fail(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data));
+    /// Lost arbitration to another controller.
+    pub const ARBITRATION_LOSS: Self = Self::Fail(ErrorKind::ArbitrationLoss);
+    /// Bus error (misplaced START or STOP condition).
+    pub const BUS_ERROR: Self = Self::Fail(ErrorKind::Bus);
+}
+
+/// A fault scheduled on an [`AS56Mock`](super::AS56Mock).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fault {
+    /// When the fault fires.
+    pub trigger: FaultTrigger,
+    /// What happens when it fires.
+    pub action: FaultAction,
+    /// How many times the fault has fired so far.
+    pub hits: u32,
+    rng: Rng,
+}
+
+impl Fault {
+    /// Creates a fault that has not fired yet.
+    pub fn new(trigger: FaultTrigger, action: FaultAction) -> Self {
+        let rng = match trigger {
+            FaultTrigger::Random { seed, .. } => Rng::new(seed),
+            _ => Rng::new(0),
+        };
+        Self {
+            trigger,
+            action,
+            hits: 0,
+            rng,
+        }
+    }
+
+    /// Returns the register this fault is limited to, if any.
+    pub(super) fn register(&self) -> Option<u8> {
+        match self.trigger {
+            FaultTrigger::Register(reg) => Some(reg),
+            _ => None,
+        }
+    }
+
+    /// Decides whether the fault fires for transaction `seq` touching `registers`.
+    pub(super) fn fires(&mut self, seq: u64, mut registers: impl Iterator<Item = u8>) -> bool {
+        let fires = match self.trigger {
+            FaultTrigger::Transaction(n) => n == seq,
+            FaultTrigger::Random { probability, .. } => self.rng.next_unit() < probability,
+            FaultTrigger::Register(reg) => registers.any(|r| r == reg),
+        };
+        if fires {
+            self.hits += 1;
+        }
+        fires
+    }
+}
@@ -0,0 +1,104 @@
+use super::clock::SIM_STEP;
+use crate::types::{Configuration, FastFilterThreshold, Hysteresis, SlowFilter};
+use core::time::Duration;
+
+const COUNTS_PER_TURN: f32 = 4096.0;
+/// Settling time of the fast filter, one sampling period of the chip.
+const FAST_SETTLING: Duration = SIM_STEP;
+
+/// Returns the step response settling time of a slow filter setting (datasheet).
+pub fn slow_filter_settling(filter: SlowFilter) -> Duration {
+    match filter {
+        SlowFilter::X16 => Duration::from_micros(2200),
+        SlowFilter::X8 => Duration::from_micros(1100),
+        SlowFilter::X4 => Duration::from_micros(550),
+        SlowFilter::X2 => Duration::from_micros(286),
+    }
+}
+
+/// Returns the position change above which the fast filter takes over, if enabled.
+pub fn fast_filter_threshold(threshold: FastFilterThreshold) -> Option<u16> {
+    match threshold {
+        FastFilterThreshold::SlowOnly => None,
+        FastFilterThreshold::Lsb6 => Some(6),
+        FastFilterThreshold::Lsb7 => Some(7),
+        FastFilterThreshold::Lsb9 => Some(9),
+        FastFilterThreshold::Lsb10 => Some(10),
+        FastFilterThreshold::Lsb18 => Some(18),
+        FastFilterThreshold::Lsb21 => Some(21),
+        FastFilterThreshold::Lsb24 => Some(24),
+    }
+}
+
+/// Returns the hysteresis deadband of the output in LSB.
+pub fn hysteresis_lsb(hysteresis: Hysteresis) -> u16 {
+    match hysteresis {
+        Hysteresis::Off => 0,
+        Hysteresis::Lsb1 => 1,
+        Hysteresis::Lsb2 => 2,
+        Hysteresis::Lsb3 => 3,
+    }
+}
+
+/// Digital filter chain between RAW_ANGLE and the ANGLE output.
+///
+/// The slow filter is modelled as a first-order low-pass whose time constant is a
+/// quarter of the datasheet settling time (98 % settled). When the input is more
+/// than the fast filter threshold away from the filtered value, the fast filter
+/// settles within one sampling period instead. The hysteresis lets the output
+/// follow movement in the same direction, but only reverses once the filtered
+/// value has moved back by more than the deadband.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct AngleFilter {
+    /// Filtered position in counts (0..4096).
+    filtered: f32,
+    /// Output after the hysteresis, in counts.
+    output: u16,
+    /// Direction of the last output change (-1, 0 or 1).
+    direction: i8,
+}
+
+impl AngleFilter {
+    /// Creates a filter settled at `raw`.
+    pub(super) fn new(raw: u16) -> Self {
+        Self {
+            filtered: raw as f32,
+            output: raw,
+            direction: 0,
+        }
+    }
+
+    /// Returns the filter output in RAW_ANGLE counts.
+    pub(super) fn output(&self) -> u16 {
+        self.output
+    }
+
+    /// Lets the filter track `raw` for `dt` with the settings of `config`.
+    pub(super) fn step(&mut self, raw: u16, config: &Configuration, dt: Duration) {
+        let error = wrap_half_turn(raw as f32 - self.filtered);
+        let fast = fast_filter_threshold(config.fast_filter_threshold)
+            .is_some_and(|threshold| error.abs() > threshold as f32);
+        let settling = if fast {
+            FAST_SETTLING
+        } else {
+            slow_filter_settling(config.slow_filter)
+        };
+        let tau = settling.as_secs_f32() / 4.0;
+        let alpha = 1.0 - (-dt.as_secs_f32() / tau).exp();
+        self.filtered = (self.filtered + alpha * error).rem_euclid(COUNTS_PER_TURN);
+
+        let target = (self.filtered.round() as u32 % 4096) as u16;
+        let change = wrap_half_turn(target as f32 - self.output as f32);
+        let direction = change.signum() as i8;
+        let deadband = hysteresis_lsb(config.hysteresis) as f32;
+        if change != 0.0 && (direction == self.direction || change.abs() > deadband) {
+            self.output = target;
+            self.direction = direction;
+        }
+    }
+}
+
+/// Wraps a difference of positions into `-2048.0..2048.0` counts.
+fn wrap_half_turn(diff: f32) -> f32 {
+    (diff + COUNTS_PER_TURN / 2.0).rem_euclid(COUNTS_PER_TURN) - COUNTS_PER_TURN / 2.0
+}
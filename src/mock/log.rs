@@ -0,0 +1,78 @@
+use embedded_hal::i2c::ErrorKind;
+use std::fmt;
+use std::vec::Vec;
+
+/// Default number of operations kept by the transaction log.
+pub const DEFAULT_LOG_CAPACITY: usize = 1024;
+
+/// Direction of a logged I2C operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Data sent from the controller to the mock.
+    Write,
+    /// Data returned by the mock.
+    Read,
+}
+
+/// One I2C operation recorded by [`AS56Mock`](super::AS56Mock).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Sequence number of the transaction the operation belongs to (starting at 1).
+    pub seq: u64,
+    /// I2C address the operation was sent to.
+    pub address: u8,
+    /// Whether data was written or read.
+    pub direction: Direction,
+    /// First register accessed by the operation.
+    pub register: u8,
+    /// Data bytes transferred, without the register address byte of a write.
+    pub bytes: Vec<u8>,
+    /// Error of the transaction, if it failed.
+    pub error: Option<ErrorKind>,
+}
+
+impl LogEntry {
+    /// Returns the byte transferred for `reg`, if this operation covered it.
+    pub fn byte_for(&self, reg: u8) -> Option<u8> {
+        let offset = reg.checked_sub(self.register)? as usize;
+        self.bytes.get(offset).copied()
+    }
+
+    /// Returns `true` if this operation transferred data for `reg`.
+    pub fn covers(&self, reg: u8) -> bool {
+        self.byte_for(reg).is_some()
+    }
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (dir, arrow) = match self.direction {
+            Direction::Write => ("W", "<-"),
+            Direction::Read => ("R", "->"),
+        };
+        write!(
+            f,
+            "#{:04} 0x{:02X} {} {:<12} {}",
+            self.seq,
+            self.address,
+            dir,
+            register_name(self.register),
+            arrow
+        )?;
+        for byte in &self.bytes {
+            write!(f, " {:02X}", byte)?;
+        }
+        if let Some(kind) = self.error {
+            write!(f, "  [FAILED: {:?}]", kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the datasheet name of a register, or its hex address for unnamed ones.
+pub fn register_name(reg: u8) -> std::borrow::Cow<'static, str> {
+    match crate::regs::register_name(reg) {
+        Some(name) => name.into(),
+        None => std::format!("0x{:02X}", reg).into(),
+    }
+}
@@ -0,0 +1,241 @@
+use super::rng::Rng;
+use core::time::Duration;
+use std::sync::Arc;
+
+/// Lower end of the recommended magnetic field range (mT). Weaker fields saturate the AGC (ML).
+pub const FIELD_MIN_MT: f32 = 30.0;
+/// Upper end of the recommended magnetic field range (mT). Stronger fields saturate the AGC (MH).
+pub const FIELD_MAX_MT: f32 = 90.0;
+/// Field below which the model considers no magnet present (MD clear).
+pub const FIELD_DETECT_MT: f32 = 10.0;
+/// MAGNITUDE reported while the AGC is able to compensate the field.
+pub const NOMINAL_MAGNITUDE: u16 = 1800;
+
+/// Radius of the modelled diametric magnet (6 mm diameter), which sets how fast
+/// the field decays with air gap and lateral offset.
+const MAGNET_RADIUS_MM: f32 = 3.0;
+/// AGC value at the weak end of the range (5 V operation).
+const AGC_MAX: f32 = 255.0;
+const COUNTS_PER_TURN: f32 = 4096.0;
+
+/// Physical setup of the magnet above an [`AS56Mock`](super::AS56Mock).
+///
+/// From these parameters and the shaft angle the mock derives RAW_ANGLE,
+/// MAGNITUDE, AGC and the MD/ML/MH status bits consistently, so tests cannot
+/// produce register combinations a real chip would never report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagnetModel {
+    /// Axial field of the magnet at its surface (mT).
+    pub strength_mt: f32,
+    /// Distance between magnet and chip surface (mm).
+    pub air_gap_mm: f32,
+    /// Distance between magnet axis and sensor center (mm).
+    pub lateral_offset_mm: f32,
+    /// Amplitude of the once-per-turn angle error caused by an eccentric
+    /// magnet or shaft (degrees).
+    pub eccentricity_deg: f32,
+    /// Standard deviation of the angle noise at nominal field strength (LSB).
+    ///
+    /// The noise grows as the field gets weaker than the AGC can compensate.
+    pub noise_lsb: f32,
+    /// Seed of the noise generator, for reproducible test runs.
+    pub seed: u64,
+}
+
+impl Default for MagnetModel {
+    /// A well-placed magnet: 63 mT at the chip, no offset, no error, no noise.
+    fn default() -> Self {
+        Self {
+            strength_mt: 150.0,
+            air_gap_mm: 1.0,
+            lateral_offset_mm: 0.0,
+            eccentricity_deg: 0.0,
+            noise_lsb: 0.0,
+            seed: 1,
+        }
+    }
+}
+
+impl MagnetModel {
+    /// Returns the field strength at the sensor (mT).
+    ///
+    /// Decays like a dipole with the air gap and the lateral offset.
+    pub fn field_mt(&self) -> f32 {
+        let axial = MAGNET_RADIUS_MM / (MAGNET_RADIUS_MM + self.air_gap_mm.max(0.0));
+        let lateral = self.lateral_offset_mm / MAGNET_RADIUS_MM;
+        self.strength_mt.max(0.0) * axial.powi(3) / (1.0 + lateral * lateral).powf(1.5)
+    }
+
+    /// Returns the AGC value the chip settles to for this field.
+    ///
+    /// The gain is proportional to the inverse field: 255 at [`FIELD_MIN_MT`]
+    /// and below, 0 at [`FIELD_MAX_MT`] and above.
+    pub fn agc(&self) -> u8 {
+        let field = self.field_mt().max(f32::EPSILON);
+        let span = 1.0 / FIELD_MIN_MT - 1.0 / FIELD_MAX_MT;
+        let agc = AGC_MAX * (1.0 / field - 1.0 / FIELD_MAX_MT) / span;
+        agc.clamp(0.0, AGC_MAX).round() as u8
+    }
+
+    /// Returns the CORDIC magnitude: nominal while the AGC compensates the field,
+    /// proportional to the field once the AGC saturates.
+    pub fn magnitude(&self) -> u16 {
+        let field = self.field_mt();
+        let compensated = field.clamp(FIELD_MIN_MT, FIELD_MAX_MT);
+        let magnitude = NOMINAL_MAGNITUDE as f32 * field / compensated;
+        magnitude.clamp(0.0, 4095.0).round() as u16
+    }
+
+    /// Returns the STATUS register bits (MD, ML, MH) for this field.
+    pub fn status(&self) -> u8 {
+        let field = self.field_mt();
+        let mut status = 0;
+        if field >= FIELD_DETECT_MT {
+            status |= 0x20;
+        }
+        if field < FIELD_MIN_MT {
+            status |= 0x10;
+        }
+        if field > FIELD_MAX_MT {
+            status |= 0x08;
+        }
+        status
+    }
+
+    /// Returns the RAW_ANGLE measured at `shaft_deg`, including the eccentricity
+    /// error and one noise sample drawn from `rng`.
+    pub(super) fn raw_angle(&self, shaft_deg: f64, rng: &mut Rng) -> u16 {
+        let shaft_rad = shaft_deg.to_radians() as f32;
+        let error_deg = self.eccentricity_deg * shaft_rad.sin();
+        let mut counts = (shaft_deg as f32 + error_deg) / 360.0 * COUNTS_PER_TURN;
+        if self.noise_lsb > 0.0 {
+            let weakening = NOMINAL_MAGNITUDE as f32 / self.magnitude().max(1) as f32;
+            counts += rng.next_gaussian() * self.noise_lsb * weakening.max(1.0);
+        }
+        counts.round().rem_euclid(COUNTS_PER_TURN) as u16
+    }
+}
+
+/// How the simulated shaft turns over virtual time.
+///
+/// Speeds are in RPM, positive values increase RAW_ANGLE. Time is measured from
+/// the moment the profile was installed with
+/// [`AS56Mock::mock_set_speed_profile`](super::AS56Mock::mock_set_speed_profile).
+#[derive(Clone)]
+pub enum SpeedProfile {
+    /// The shaft does not move.
+    Stopped,
+    /// Constant speed.
+    Constant(f32),
+    /// Linear change from `start_rpm` to `end_rpm` over `duration`, then holds `end_rpm`.
+    Ramp {
+        /// Speed at the start of the ramp.
+        start_rpm: f32,
+        /// Speed at and after the end of the ramp.
+        end_rpm: f32,
+        /// Length of the ramp.
+        duration: Duration,
+    },
+    /// Sinusoidal back-and-forth motion around the starting position.
+    Oscillate {
+        /// Peak deflection from the starting position (degrees).
+        amplitude_deg: f32,
+        /// Duration of one full swing.
+        period: Duration,
+    },
+    /// Arbitrary speed as a function of the time since the profile started.
+    Custom(Arc<dyn Fn(Duration) -> f32 + Send + Sync>),
+}
+
+impl SpeedProfile {
+    /// Returns the shaft speed in degrees per second at time `t`.
+    pub fn deg_per_sec(&self, t: Duration) -> f64 {
+        let rpm = match self {
+            SpeedProfile::Stopped => 0.0,
+            SpeedProfile::Constant(rpm) => *rpm as f64,
+            SpeedProfile::Ramp {
+                start_rpm,
+                end_rpm,
+                duration,
+            } => {
+                let progress = if duration.is_zero() {
+                    1.0
+                } else {
+                    (t.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+                };
+                *start_rpm as f64 + (*end_rpm as f64 - *start_rpm as f64) * progress
+            }
+            SpeedProfile::Oscillate {
+                amplitude_deg,
+                period,
+            } => {
+                if period.is_zero() {
+                    return 0.0;
+                }
+                let omega = core::f64::consts::TAU / period.as_secs_f64();
+                return *amplitude_deg as f64 * omega * (omega * t.as_secs_f64()).cos();
+            }
+            SpeedProfile::Custom(f) => f(t) as f64,
+        };
+        rpm * 6.0
+    }
+}
+
+impl core::fmt::Debug for SpeedProfile {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SpeedProfile::Stopped => write!(f, "Stopped"),
+            SpeedProfile::Constant(rpm) => f.debug_tuple("Constant").field(rpm).finish(),
+            SpeedProfile::Ramp {
+                start_rpm,
+                end_rpm,
+                duration,
+            } => f
+                .debug_struct("Ramp")
+                .field("start_rpm", start_rpm)
+                .field("end_rpm", end_rpm)
+                .field("duration", duration)
+                .finish(),
+            SpeedProfile::Oscillate {
+                amplitude_deg,
+                period,
+            } => f
+                .debug_struct("Oscillate")
+                .field("amplitude_deg", amplitude_deg)
+                .field("period", period)
+                .finish(),
+            SpeedProfile::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Magnet simulation state kept by the mock.
+pub(super) struct MagnetSim {
+    pub(super) model: MagnetModel,
+    /// Shaft position in degrees (0..360).
+    pub(super) shaft_deg: f64,
+    pub(super) profile: SpeedProfile,
+    /// Virtual time at which `profile` was installed.
+    pub(super) profile_start: Duration,
+    pub(super) rng: Rng,
+}
+
+impl MagnetSim {
+    pub(super) fn new(model: MagnetModel, shaft_deg: f64) -> Self {
+        Self {
+            model,
+            shaft_deg,
+            profile: SpeedProfile::Stopped,
+            profile_start: Duration::ZERO,
+            rng: Rng::new(model.seed),
+        }
+    }
+
+    /// Moves the shaft from virtual time `from` by `dt` following the speed profile.
+    pub(super) fn step(&mut self, from: Duration, dt: Duration) {
+        // Midpoint rule, exact for constant speeds and ramps.
+        let mid = (from + dt / 2).saturating_sub(self.profile_start);
+        self.shaft_deg += self.profile.deg_per_sec(mid) * dt.as_secs_f64();
+        self.shaft_deg = self.shaft_deg.rem_euclid(360.0);
+    }
+}
@@ -0,0 +1,175 @@
+use super::{AS56Mock, MockError};
+use crate::mux::{MUX_CHANNELS, TCA9548A_DEFAULT_ADDR};
+use embedded_hal::i2c::{I2c, Operation, SevenBitAddress};
+use std::sync::{Arc, Mutex};
+
+/// Internal state of the emulated multiplexer.
+struct MuxState {
+    address: u8,
+    control: u8,
+    select_writes: usize,
+    channels: [Option<AS56Mock>; MUX_CHANNELS as usize],
+}
+
+/// A mock TCA9548A/PCA9548 multiplexer with an [`AS56Mock`] on any of its channels.
+///
+/// Transactions addressed to the multiplexer update its control register; all other
+/// transactions are forwarded to the device on the enabled channel. A transaction with
+/// no enabled device fails like a missing ACK, and so does one with several enabled
+/// devices (they would all answer at the same address).
+///
+/// Like [`AS56Mock`], clones share the same state, so a test can keep a handle to
+/// inspect the channel switching while the bus is owned by the code under test.
+#[derive(Clone)]
+pub struct MockMux {
+    state: Arc<Mutex<MuxState>>,
+}
+
+impl MockMux {
+    /// Creates a multiplexer at the default address (0x70) with no devices attached.
+    pub fn new() -> Self {
+        Self::with_address(TCA9548A_DEFAULT_ADDR)
+    }
+
+    /// Creates a multiplexer at a custom address with no devices attached.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MuxState {
+                address,
+                control: 0,
+                select_writes: 0,
+                channels: Default::default(),
+            })),
+        }
+    }
+
+    /// Creates a multiplexer with a fresh [`AS56Mock`] on each of the first `count` channels.
+    pub fn with_devices(count: u8) -> Self {
+        let mux = Self::new();
+        for channel in 0..count.min(MUX_CHANNELS) {
+            mux.mock_attach(channel, AS56Mock::new());
+        }
+        mux
+    }
+
+    // --- Simulation Controller API ---
+
+    /// Connects `device` to `channel` (0..=7), replacing any previous device.
+    ///
+    /// # Panics
+    /// Panics if `channel` is not 0..=7.
+    pub fn mock_attach(&self, channel: u8, device: AS56Mock) {
+        assert!(
+            channel < MUX_CHANNELS,
+            "mux channel {} out of range (0..={})",
+            channel,
+            MUX_CHANNELS - 1
+        );
+        let mut state = self.state.lock().unwrap();
+        state.channels[channel as usize] = Some(device);
+    }
+
+    /// Returns a handle to the device on `channel`, if any.
+    pub fn mock_device(&self, channel: u8) -> Option<AS56Mock> {
+        let state = self.state.lock().unwrap();
+        state.channels.get(channel as usize)?.clone()
+    }
+
+    /// Returns the control register (one bit per enabled channel).
+    pub fn mock_control(&self) -> u8 {
+        self.state.lock().unwrap().control
+    }
+
+    /// Returns how many times the control register has been written.
+    pub fn mock_select_count(&self) -> usize {
+        self.state.lock().unwrap().select_writes
+    }
+
+    /// Returns the device that answers `address` on the currently enabled channels.
+    fn target(&self, address: u8) -> Result<AS56Mock, MockError> {
+        let state = self.state.lock().unwrap();
+        let mut enabled = state
+            .channels
+            .iter()
+            .enumerate()
+            .filter(|(ch, _)| state.control & (1 << ch) != 0)
+            .filter_map(|(_, dev)| dev.clone());
+        match (enabled.next(), enabled.next()) {
+            (Some(dev), None) => Ok(dev),
+            _ => Err(MockError::NotAcknowledged(address)),
+        }
+    }
+
+    /// Handles a transaction addressed to the multiplexer itself.
+    fn control_transaction(&self, operations: &mut [Operation<'_>]) {
+        let mut state = self.state.lock().unwrap();
+        for op in operations {
+            match op {
+                Operation::Write(bytes) => {
+                    if let Some(&control) = bytes.last() {
+                        state.control = control;
+                        state.select_writes += 1;
+                    }
+                }
+                Operation::Read(buf) => buf.fill(state.control),
+            }
+        }
+    }
+
+    fn is_mux(&self, address: u8) -> bool {
+        self.state.lock().unwrap().address == address
+    }
+}
+
+impl Default for MockMux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl embedded_hal::i2c::ErrorType for MockMux {
+    type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for MockMux {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        if self.is_mux(address) {
+            self.control_transaction(&mut [Operation::Read(read)]);
+            return Ok(());
+        }
+        self.target(address)?.read(address, read)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        if self.is_mux(address) {
+            self.control_transaction(&mut [Operation::Write(write)]);
+            return Ok(());
+        }
+        self.target(address)?.write(address, write)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        if self.is_mux(address) {
+            self.control_transaction(&mut [Operation::Write(write), Operation::Read(read)]);
+            return Ok(());
+        }
+        self.target(address)?.write_read(address, write, read)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if self.is_mux(address) {
+            self.control_transaction(operations);
+            return Ok(());
+        }
+        self.target(address)?.transaction(address, operations)
+    }
+}
@@ -0,0 +1,58 @@
+/// Maximum number of angle (ZPOS/MPOS) burns the chip accepts.
+pub const MAX_ANGLE_BURNS: u8 = 3;
+
+/// Simulated one-time-programmable memory of an [`AS56Mock`](super::AS56Mock).
+///
+/// A fresh chip has all OTP cells at zero. Burns copy the volatile programming
+/// registers into these cells, and the registers are reloaded from them on a
+/// simulated power cycle or OTP reload command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MockOtp {
+    /// Burned start position.
+    pub zpos: u16,
+    /// Burned stop position.
+    pub mpos: u16,
+    /// Burned maximum angle.
+    pub mang: u16,
+    /// Burned CONF_HI (bits 5:0) and CONF_LO.
+    pub conf: u16,
+    /// Burned I2C_ADDR, address in bits 7:1 (AS5600L only; zero on an AS5600).
+    pub i2c_addr: u8,
+    /// Number of angle burns performed (the ZMCO counter).
+    pub zmco: u8,
+    /// Whether the one-time setting burn (MANG/CONF) has been performed.
+    pub settings_burned: bool,
+    /// Number of burn commands the chip ignored because a precondition was not met.
+    pub refused_burns: u32,
+}
+
+impl MockOtp {
+    /// Performs an angle burn, or refuses it like the chip does.
+    ///
+    /// Refused without a magnet or once [`MAX_ANGLE_BURNS`] burns have been used.
+    pub(super) fn burn_angle(&mut self, zpos: u16, mpos: u16, magnet_detected: bool) -> bool {
+        if !magnet_detected || self.zmco >= MAX_ANGLE_BURNS {
+            self.refused_burns += 1;
+            return false;
+        }
+        self.zpos = zpos;
+        self.mpos = mpos;
+        self.zmco += 1;
+        true
+    }
+
+    /// Performs the setting burn (MANG, CONF and I2C_ADDR), or refuses it like the chip does.
+    ///
+    /// Refused if it was already performed or an angle burn has happened (ZMCO != 0).
+    pub(super) fn burn_setting(&mut self, mang: u16, conf: u16, i2c_addr: u8) -> bool {
+        if self.settings_burned || self.zmco != 0 {
+            self.refused_burns += 1;
+            return false;
+        }
+        self.mang = mang;
+        self.conf = conf;
+        self.i2c_addr = i2c_addr;
+        self.settings_burned = true;
+        true
+    }
+}
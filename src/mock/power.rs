@@ -0,0 +1,88 @@
+use super::clock::SIM_STEP;
+use crate::types::PowerMode;
+use core::time::Duration;
+
+/// Position band the watchdog considers "not moving" (datasheet: 4 LSB).
+pub const WATCHDOG_THRESHOLD_LSB: u16 = 4;
+/// Time without movement after which the watchdog enters LPM3 (datasheet: 1 minute).
+pub const WATCHDOG_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Returns how often the chip samples the magnet in a power mode (datasheet).
+pub fn polling_interval(mode: PowerMode) -> Duration {
+    match mode {
+        PowerMode::Nominal => SIM_STEP,
+        PowerMode::LPM1 => Duration::from_millis(5),
+        PowerMode::LPM2 => Duration::from_millis(20),
+        PowerMode::LPM3 => Duration::from_millis(100),
+    }
+}
+
+/// Power mode and watchdog emulation state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct PowerSim {
+    /// Time of the last output update.
+    last_poll: Duration,
+    /// Time of the next output update.
+    next_poll: Duration,
+    /// Raw angle set by hand, waiting for the next poll to be sampled.
+    pub(super) pending_raw: Option<u16>,
+    /// Position the watchdog measures movement against.
+    anchor: u16,
+    /// Time since which the position stayed within the watchdog band.
+    still_since: Duration,
+    /// Whether the watchdog forced the chip into LPM3.
+    pub(super) sleeping: bool,
+}
+
+impl PowerSim {
+    /// Creates the emulation at time `now` with the chip awake at `raw`.
+    pub(super) fn new(now: Duration, raw: u16) -> Self {
+        Self {
+            last_poll: now,
+            next_poll: now,
+            pending_raw: None,
+            anchor: raw,
+            still_since: now,
+            sleeping: false,
+        }
+    }
+
+    /// Returns the power mode the chip currently runs in.
+    pub(super) fn effective_mode(&self, configured: PowerMode) -> PowerMode {
+        if self.sleeping {
+            PowerMode::LPM3
+        } else {
+            configured
+        }
+    }
+
+    /// Decides whether the chip samples at `now`.
+    ///
+    /// Returns the time since the previous sample if it does.
+    pub(super) fn poll(&mut self, now: Duration, configured: PowerMode) -> Option<Duration> {
+        if now < self.next_poll {
+            return None;
+        }
+        let elapsed = now - self.last_poll;
+        self.last_poll = now;
+        self.next_poll = now + polling_interval(self.effective_mode(configured));
+        Some(elapsed)
+    }
+
+    /// Updates the watchdog with the position sampled at `now`.
+    pub(super) fn watchdog(&mut self, raw: u16, enabled: bool, now: Duration) {
+        let diff = raw.wrapping_sub(self.anchor) & 0x0FFF;
+        let distance = diff.min(4096 - diff);
+        if !enabled || distance > WATCHDOG_THRESHOLD_LSB {
+            // Movement wakes the chip, the next poll runs at the configured rate.
+            if self.sleeping {
+                self.next_poll = now;
+            }
+            self.anchor = raw;
+            self.still_since = now;
+            self.sleeping = false;
+        } else if now - self.still_since >= WATCHDOG_TIMEOUT {
+            self.sleeping = true;
+        }
+    }
+}
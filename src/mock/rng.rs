@@ -0,0 +1,28 @@
+/// Small deterministic xorshift64* generator for reproducible simulations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator from `seed` (any value, zero included).
+    pub(super) fn new(seed: u64) -> Self {
+        // xorshift must not start from zero
+        Self(seed | 1)
+    }
+
+    /// Draws a number in `0.0..1.0`.
+    pub(super) fn next_unit(&mut self) -> f32 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let value = self.0.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (value >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Draws a standard normal sample (Box-Muller transform).
+    pub(super) fn next_gaussian(&mut self) -> f32 {
+        // 1 - u is in (0, 1], so the logarithm stays finite.
+        let u1 = 1.0 - self.next_unit();
+        let u2 = self.next_unit();
+        (-2.0 * u1.ln()).sqrt() * (core::f32::consts::TAU * u2).cos()
+    }
+}
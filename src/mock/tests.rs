@@ -0,0 +1,766 @@
+use super::*;
+use crate::AS5600Driver;
+use crate::traits::AS5600Interface;
+use embedded_hal::i2c::I2c;
+
+const ADDR: u8 = DEFAULT_ADDR;
+
+// --- ANGLE scaling ---
+
+/// Checks `scale_angle` against `(raw, expected ANGLE)` pairs for one set of
+/// ZPOS, MPOS and MANG.
+fn check_scaling(zpos: u16, mpos: u16, mang: u16, cases: &[(u16, u16)]) {
+    for &(raw, angle) in cases {
+        assert_eq!(
+            scale_angle(raw, zpos, mpos, mang),
+            angle,
+            "raw {raw} with ZPOS {zpos}, MPOS {mpos}, MANG {mang}"
+        );
+    }
+}
+
+#[test]
+fn angle_without_range_is_offset_by_zpos() {
+    check_scaling(0, 0, 0, &[(0, 0), (1234, 1234), (4095, 4095)]);
+    check_scaling(1000, 0, 0, &[(1000, 0), (999, 4095), (0, 3096)]);
+    // MPOS equal to ZPOS spans a full turn.
+    check_scaling(100, 100, 0, &[(100, 0), (50, 4046)]);
+}
+
+#[test]
+fn angle_is_stretched_from_zpos_to_mpos() {
+    // 1024 counts stretched by 4, the 3072 count gap split at its midpoint (3560).
+    check_scaling(
+        1000,
+        2024,
+        0,
+        &[
+            (1000, 0),
+            (1512, 2048),
+            (2023, 4092),
+            (2024, 4095),
+            (3560, 4095),
+            (3561, 0),
+            (999, 0),
+        ],
+    );
+    // MPOS takes precedence over MANG.
+    check_scaling(0, 1024, 2048, &[(512, 2048), (1024, 4095)]);
+}
+
+#[test]
+fn angle_range_wraps_when_zpos_is_above_mpos() {
+    // 3000 -> 1000 through 0 is 2096 counts, the 2000 count gap ends at 2000.
+    check_scaling(
+        3000,
+        1000,
+        0,
+        &[
+            (3000, 0),
+            (4095, 2139),
+            (0, 2141),
+            (1000, 4095),
+            (2000, 4095),
+            (2001, 0),
+            (2999, 0),
+        ],
+    );
+}
+
+#[test]
+fn angle_range_from_mang_alone() {
+    check_scaling(
+        0,
+        0,
+        2048,
+        &[(0, 0), (1024, 2048), (2048, 4095), (3072, 4095), (3073, 0)],
+    );
+    // 1000 counts from ZPOS 500, the gap midpoint is 1548 counts past 1500.
+    check_scaling(
+        500,
+        0,
+        1000,
+        &[
+            (1000, 2048),
+            (1500, 4095),
+            (3048, 4095),
+            (3049, 0),
+            (499, 0),
+        ],
+    );
+}
+
+// --- Register pointer ---
+
+#[test]
+fn bare_reads_repeat_the_angle_register() {
+    let mut mock = AS56Mock::new();
+    mock.mock_set_raw_angle(0x0123);
+    let mut buf = [0u8; 2];
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0x01, 0x23]);
+
+    mock.mock_set_raw_angle(0x0ABC);
+    mock.read(ADDR, &mut buf).unwrap();
+    assert_eq!(buf, [0x0A, 0xBC]);
+    let mut byte = [0u8];
+    mock.read(ADDR, &mut byte).unwrap();
+    mock.read(ADDR, &mut byte).unwrap();
+    assert_eq!(byte, [0xBC]);
+}
+
+#[test]
+fn write_read_wraps_within_the_register_pair() {
+    let mut mock = AS56Mock::new();
+    mock.mock_set_raw_angle(0x0ABC);
+    mock.mock_set_magnitude(0x0456);
+    let mut buf = [0u8; 5];
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0x0A, 0xBC, 0x0A, 0xBC, 0x0A]);
+    mock.write_read(ADDR, &[regs::MAGNITUDE_HI], &mut buf[..3])
+        .unwrap();
+    assert_eq!(buf[..3], [0x04, 0x56, 0x04]);
+
+    // Starting at the LO byte the pointer auto-increments into ANGLE_HI.
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_LO], &mut buf[..2])
+        .unwrap();
+    assert_eq!(buf[..2], [0xBC, 0x0A]);
+}
+
+#[test]
+fn bursts_run_through_the_angle_registers() {
+    let mut mock = AS56Mock::new();
+    mock.mock_set_raw_angle(0x0ABC);
+    let mut buf = [0u8; 5];
+    mock.write_read(ADDR, &[regs::STATUS], &mut buf).unwrap();
+    assert_eq!(buf, [0x20, 0x0A, 0xBC, 0x0A, 0xBC]);
+
+    // The pointer continues after ANGLE_LO.
+    let mut byte = [0xFF];
+    mock.read(ADDR, &mut byte).unwrap();
+    assert_eq!(byte, [0x00]);
+}
+
+#[test]
+fn transactions_keep_the_wrap_across_reads() {
+    let mut mock = AS56Mock::new();
+    mock.mock_set_raw_angle(0x0ABC);
+    let (mut first, mut second, mut agc) = ([0u8; 2], [0u8; 2], [0u8]);
+    mock.transaction(
+        ADDR,
+        &mut [
+            Operation::Write(&[regs::ANGLE_HI]),
+            Operation::Read(&mut first),
+            Operation::Read(&mut second),
+            Operation::Write(&[regs::AGC]),
+            Operation::Read(&mut agc),
+        ],
+    )
+    .unwrap();
+    assert_eq!((first, second, agc), ([0x0A, 0xBC], [0x0A, 0xBC], [100]));
+
+    let mut sensor = AS5600Driver::new(mock.clone());
+    let snapshot = sensor.read_snapshot().unwrap();
+    assert_eq!((snapshot.raw_angle, snapshot.angle), (0x0ABC, 0x0ABC));
+    assert_eq!(snapshot.agc, 100);
+}
+
+// --- Fault injection ---
+
+/// Returns which of `count` raw angle reads fail.
+fn failing_reads(mock: &AS56Mock, count: usize) -> Vec<bool> {
+    let mut sensor = AS5600Driver::new(mock.clone());
+    (0..count)
+        .map(|_| sensor.read_raw_angle().is_err())
+        .collect()
+}
+
+#[test]
+fn random_faults_follow_the_seed() {
+    let mock = AS56Mock::new();
+    mock.mock_fail_random(0.3, 7, FaultAction::BUS_ERROR);
+    let failures = failing_reads(&mock, 16);
+
+    // Transactions 3, 4, 8 and 15 fail with seed 7.
+    let failed: Vec<usize> = (1..=16).filter(|&n| failures[n - 1]).collect();
+    assert_eq!(failed, [3, 4, 8, 15]);
+    // The fault draws one number per transaction from its own generator.
+    let mut rng = Rng::new(7);
+    assert!(
+        failures
+            .iter()
+            .all(|&failed| failed == (rng.next_unit() < 0.3))
+    );
+    assert_eq!(mock.mock_faults()[0].hits, 4);
+
+    // The same seed fails the same transactions, and never or always are exact.
+    let again = AS56Mock::new();
+    again.mock_fail_random(0.3, 7, FaultAction::BUS_ERROR);
+    assert_eq!(failing_reads(&again, 16), failures);
+    let never = AS56Mock::new();
+    never.mock_fail_random(0.0, 7, FaultAction::BUS_ERROR);
+    assert_eq!(failing_reads(&never, 16), [false; 16]);
+    let always = AS56Mock::new();
+    always.mock_fail_random(1.0, 7, FaultAction::BUS_ERROR);
+    assert_eq!(failing_reads(&always, 16), [true; 16]);
+}
+
+#[test]
+fn register_faults_hit_only_transfers_of_that_register() {
+    let mock = AS56Mock::new();
+    mock.mock_fail_register(regs::AGC, FaultAction::NACK_DATA);
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.read_raw_angle().unwrap();
+    sensor.get_magnitude().unwrap();
+    assert!(matches!(sensor.get_agc(), Err(crate::AS56Error::I2c(_))));
+    // The snapshot reads AGC..MAGNITUDE in the same transaction.
+    assert!(sensor.read_snapshot().is_err());
+    sensor.set_zero_position(10).unwrap();
+    assert_eq!(mock.mock_faults()[0].hits, 2);
+    assert_eq!(mock.mock_transaction_count(), 5);
+
+    mock.mock_clear_faults();
+    assert_eq!(sensor.get_agc().unwrap(), 100);
+}
+
+#[test]
+fn corrupt_faults_flip_the_returned_bits() {
+    let mut mock = AS56Mock::new();
+    mock.mock_set_raw_angle(0x0123);
+    // The second transaction from now reads corrupted data.
+    mock.mock_inject_fault(
+        FaultTrigger::Transaction(mock.mock_transaction_count() + 2),
+        FaultAction::Corrupt(0xFF),
+    );
+    let mut buf = [0u8; 2];
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0x01, 0x23]);
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0xFE, 0xDC]);
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0x01, 0x23]);
+
+    // A register trigger only corrupts the bytes of that register.
+    mock.mock_fail_register(regs::AGC, FaultAction::Corrupt(0x01));
+    let snapshot = AS5600Driver::new(mock.clone()).read_snapshot().unwrap();
+    assert_eq!((snapshot.raw_angle, snapshot.agc), (0x0123, 101));
+    assert_eq!(snapshot.magnitude, 0);
+
+    // Writes go through untouched.
+    mock.write(ADDR, &[regs::ZPOS_HI, 0x01, 0x23]).unwrap();
+    mock.mock_clear_faults();
+    mock.write_read(ADDR, &[regs::ZPOS_HI], &mut buf).unwrap();
+    assert_eq!(buf, [0x01, 0x23]);
+}
+
+// --- OTP ---
+
+struct NoDelay;
+
+impl embedded_hal::delay::DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[test]
+fn fourth_angle_burn_is_refused() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    for burn in 1..=MAX_ANGLE_BURNS {
+        sensor.set_zero_position(burn as u16 * 100).unwrap();
+        unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+        assert_eq!(sensor.get_burn_count().unwrap(), burn);
+    }
+    assert_eq!(mock.mock_otp().zpos, 300);
+
+    // The driver refuses before writing, and the chip ignores a raw burn command.
+    sensor.set_zero_position(400).unwrap();
+    let err = unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap_err();
+    assert_eq!(err, crate::AS56Error::BurnLimitReached(3));
+    unsafe { sensor.danger_permanent_burn_settings() }.unwrap();
+    let otp = mock.mock_otp();
+    assert_eq!((otp.zpos, otp.zmco, otp.refused_burns), (300, 3, 1));
+}
+
+#[test]
+fn second_setting_burn_is_refused() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_max_angle(2048).unwrap();
+    unsafe { sensor.danger_verified_burn_config(&mut NoDelay) }.unwrap();
+    assert!(mock.mock_otp().settings_burned);
+
+    sensor.set_max_angle(1024).unwrap();
+    unsafe { sensor.danger_permanent_burn_config() }.unwrap();
+    let otp = mock.mock_otp();
+    assert_eq!((otp.mang, otp.refused_burns), (2048, 1));
+    sensor.reload_otp().unwrap();
+    assert_eq!(sensor.get_max_angle().unwrap(), 2048);
+}
+
+#[test]
+fn burned_values_survive_reload_and_power_cycle() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_max_angle(3000).unwrap();
+    sensor.set_slow_filter(SlowFilter::X4).unwrap();
+    unsafe { sensor.danger_verified_burn_config(&mut NoDelay) }.unwrap();
+    sensor.set_zero_position(100).unwrap();
+    sensor.set_max_position(2100).unwrap();
+    unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+
+    let check = |sensor: &mut AS5600Driver<AS56Mock>| {
+        assert_eq!(sensor.get_zero_position().unwrap(), 100);
+        assert_eq!(sensor.get_max_position().unwrap(), 2100);
+        assert_eq!(sensor.get_max_angle().unwrap(), 3000);
+        let config = sensor.get_config().unwrap();
+        assert_eq!(
+            (config.slow_filter, config.watchdog),
+            (SlowFilter::X4, true)
+        );
+    };
+
+    sensor.set_zero_position(7).unwrap();
+    sensor.set_max_angle(8).unwrap();
+    sensor.set_slow_filter(SlowFilter::X2).unwrap();
+    mock.mock_clear_log();
+    sensor.reload_otp().unwrap();
+    mock.mock_assert_write(regs::BURN, &[0x01]);
+    mock.mock_assert_write(regs::BURN, &[0x11]);
+    mock.mock_assert_write(regs::BURN, &[0x10]);
+    check(&mut sensor);
+
+    sensor.set_zero_position(7).unwrap();
+    sensor.set_max_position(9).unwrap();
+    mock.mock_power_cycle();
+    check(&mut sensor);
+    assert_eq!(sensor.get_burn_count().unwrap(), 1);
+}
+
+#[test]
+fn reload_needs_the_full_sequence() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_zero_position(100).unwrap();
+    unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+    sensor.set_zero_position(7).unwrap();
+
+    let mut bus = mock.clone();
+    bus.write(ADDR, &[regs::BURN, 0x01]).unwrap();
+    bus.write(ADDR, &[regs::BURN, 0x10]).unwrap();
+    assert_eq!(sensor.get_zero_position().unwrap(), 7);
+}
+
+// --- Power cycle and strict writes ---
+
+#[test]
+fn power_cycle_clears_volatile_registers_and_keeps_otp() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_zero_position(100).unwrap();
+    unsafe { sensor.danger_verified_burn_settings(&mut NoDelay) }.unwrap();
+    sensor.set_zero_position(200).unwrap();
+    sensor.set_max_angle(1000).unwrap();
+    sensor.set_power_mode(PowerMode::LPM3).unwrap();
+    mock.mock_set_raw_angle(1234);
+    let otp = mock.mock_otp();
+
+    mock.mock_power_cycle();
+    assert_eq!(mock.mock_otp(), otp);
+    assert_eq!(sensor.get_zero_position().unwrap(), 100);
+    assert_eq!(sensor.get_max_angle().unwrap(), 0);
+    assert_eq!(sensor.get_config().unwrap().power_mode, PowerMode::Nominal);
+    assert_eq!(sensor.get_burn_count().unwrap(), 1);
+    // The output registers keep following the magnet.
+    assert_eq!(sensor.read_raw_angle().unwrap(), 1234);
+    assert!(sensor.get_magnet_status().unwrap().detected);
+}
+
+#[test]
+fn power_cycle_resets_the_register_pointer() {
+    let mut mock = AS56Mock::new();
+    let mut buf = [0u8; 2];
+    mock.write_read(ADDR, &[regs::RAW_ANGLE_HI], &mut buf)
+        .unwrap();
+    mock.mock_power_cycle();
+    let mut zmco = [0xFF];
+    mock.read(ADDR, &mut zmco).unwrap();
+    assert_eq!(zmco, [0]);
+}
+
+#[test]
+fn strict_writes_reject_read_only_registers() {
+    let mock = AS56Mock::new();
+    mock.mock_set_raw_angle(1234);
+    let mut bus = mock.clone();
+
+    // Ignored by default, like the chip.
+    bus.write(ADDR, &[regs::RAW_ANGLE_HI, 0x00, 0x00]).unwrap();
+    bus.write(ADDR, &[regs::ZMCO, 3]).unwrap();
+
+    mock.mock_set_strict_writes(true);
+    for reg in [
+        regs::ZMCO,
+        regs::STATUS,
+        regs::RAW_ANGLE_LO,
+        regs::ANGLE_HI,
+        regs::AGC,
+        regs::MAGNITUDE_LO,
+    ] {
+        assert_eq!(bus.write(ADDR, &[reg, 0]), Err(MockError::ReadOnly(reg)));
+    }
+    // A write running into a read-only register fails as a whole.
+    assert_eq!(
+        bus.write(ADDR, &[regs::CONF_LO, 0x01, 0x00, 0x00, 0x00]),
+        Err(MockError::ReadOnly(regs::STATUS))
+    );
+    bus.write(ADDR, &[regs::ZPOS_HI, 0x01, 0x00]).unwrap();
+
+    let mut sensor = AS5600Driver::new(mock.clone());
+    assert_eq!(sensor.read_raw_angle().unwrap(), 1234);
+    assert_eq!(sensor.get_burn_count().unwrap(), 0);
+    assert_eq!(sensor.get_config().unwrap().power_mode, PowerMode::Nominal);
+    assert_eq!(sensor.get_zero_position().unwrap(), 0x100);
+}
+
+// --- Address ---
+
+#[test]
+fn only_the_chip_address_is_acknowledged() {
+    let mut mock = AS56Mock::new();
+    let mut buf = [0u8; 1];
+    assert_eq!(
+        mock.write_read(0x37, &[regs::AGC], &mut buf),
+        Err(MockError::NotAcknowledged(0x37))
+    );
+    assert_eq!(
+        mock.mock_log()[0].error,
+        Some(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+    );
+
+    // An AS5600 has no programmable address, I2C_UPDT is a plain register.
+    mock.write(ADDR, &[regs::I2C_UPDT, 0x82]).unwrap();
+    assert_eq!(mock.mock_address(), ADDR);
+    mock.write_read(ADDR, &[regs::AGC], &mut buf).unwrap();
+}
+
+#[test]
+fn as5600l_moves_to_the_address_written_to_i2c_updt() {
+    let mut mock = AS56Mock::new_as5600l();
+    let mut buf = [0u8; 1];
+    mock.write_read(AS5600L_DEFAULT_ADDR, &[regs::I2C_ADDR], &mut buf)
+        .unwrap();
+    assert_eq!(buf, [0x80]);
+
+    mock.write(AS5600L_DEFAULT_ADDR, &[regs::I2C_UPDT, 0x82])
+        .unwrap();
+    assert_eq!(mock.mock_address(), 0x41);
+    assert_eq!(
+        mock.write(AS5600L_DEFAULT_ADDR, &[regs::ZPOS_HI, 0, 1]),
+        Err(MockError::NotAcknowledged(AS5600L_DEFAULT_ADDR))
+    );
+    mock.write_read(0x41, &[regs::I2C_UPDT], &mut buf).unwrap();
+    assert_eq!(buf, [0x82]);
+
+    // Not burned, so a power cycle brings back the factory address.
+    mock.mock_power_cycle();
+    assert_eq!(mock.mock_address(), AS5600L_DEFAULT_ADDR);
+}
+
+// --- Magnet model ---
+
+fn magnet(air_gap_mm: f32) -> MagnetModel {
+    MagnetModel {
+        air_gap_mm,
+        ..MagnetModel::default()
+    }
+}
+
+#[test]
+fn shaft_turns_at_the_profile_speed() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    mock.mock_set_shaft_angle(0.0);
+    // 60 RPM is 360 degrees per second.
+    mock.mock_set_speed_profile(SpeedProfile::Constant(60.0));
+    mock.mock_advance(Duration::from_millis(250));
+    assert_eq!(mock.mock_now(), Duration::from_millis(250));
+    assert!((mock.mock_shaft_angle().unwrap() - 90.0).abs() < 1e-3);
+    assert_eq!(sensor.read_raw_angle().unwrap(), 1024);
+
+    mock.mock_set_speed_profile(SpeedProfile::Constant(-30.0));
+    mock.mock_advance(Duration::from_millis(500));
+    assert_eq!(sensor.read_raw_angle().unwrap(), 0);
+
+    // A ramp from 0 to 60 RPM covers half a turn in one second.
+    mock.mock_set_speed_profile(SpeedProfile::Ramp {
+        start_rpm: 0.0,
+        end_rpm: 60.0,
+        duration: Duration::from_secs(1),
+    });
+    mock.mock_advance(Duration::from_secs(1));
+    assert_eq!(sensor.read_raw_angle().unwrap(), 2048);
+}
+
+#[test]
+fn noise_is_bounded_and_reproducible() {
+    let sample = |seed| {
+        let mock = AS56Mock::new();
+        mock.mock_set_shaft_angle(90.0);
+        mock.mock_set_magnet(MagnetModel {
+            noise_lsb: 1.0,
+            seed,
+            ..MagnetModel::default()
+        });
+        let mut sensor = AS5600Driver::new(mock.clone());
+        (0..2000)
+            .map(|_| {
+                mock.mock_advance(SIM_STEP);
+                sensor.read_raw_angle().unwrap() as f32 - 1024.0
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let errors = sample(42);
+    assert_eq!(errors, sample(42));
+    assert_ne!(errors, sample(7));
+    assert!(errors.iter().all(|e| e.abs() <= 6.0));
+    let mean = errors.iter().sum::<f32>() / errors.len() as f32;
+    let variance = errors.iter().map(|e| (e - mean).powi(2)).sum::<f32>() / errors.len() as f32;
+    assert!(mean.abs() < 0.1, "mean {mean}");
+    assert!(
+        (0.8..1.2).contains(&variance.sqrt()),
+        "sigma {}",
+        variance.sqrt()
+    );
+}
+
+#[test]
+fn field_readings_follow_the_air_gap() {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    let mut read = |air_gap_mm| {
+        mock.mock_set_magnet(magnet(air_gap_mm));
+        let status = sensor.get_magnet_status().unwrap();
+        let flags = (status.detected, status.too_weak, status.too_strong);
+        (
+            flags,
+            sensor.get_agc().unwrap(),
+            sensor.get_magnitude().unwrap(),
+        )
+    };
+
+    // Too close: the AGC bottoms out and MAGNITUDE rises with the field.
+    let (flags, agc, magnitude) = read(0.2);
+    assert_eq!((flags, agc), ((true, false, true), 0));
+    assert!(magnitude > NOMINAL_MAGNITUDE, "{magnitude}");
+
+    // In range: the AGC compensates, more gain for a larger gap.
+    let (flags, near_agc, magnitude) = read(1.0);
+    assert_eq!(
+        (flags, magnitude),
+        ((true, false, false), NOMINAL_MAGNITUDE)
+    );
+    let (flags, far_agc, magnitude) = read(2.0);
+    assert_eq!(
+        (flags, magnitude),
+        ((true, false, false), NOMINAL_MAGNITUDE)
+    );
+    assert!(
+        0 < near_agc && near_agc < far_agc && far_agc < 255,
+        "{near_agc} {far_agc}"
+    );
+    assert_eq!(near_agc, magnet(1.0).agc());
+
+    // Too far: the AGC saturates and MAGNITUDE drops.
+    let (flags, agc, magnitude) = read(3.0);
+    assert_eq!((flags, agc), ((true, true, false), 255));
+    assert!(magnitude < NOMINAL_MAGNITUDE, "{magnitude}");
+
+    // Out of reach: no magnet detected.
+    let (flags, _, _) = read(6.0);
+    assert_eq!(flags, (false, true, false));
+}
+
+// --- Filters ---
+
+fn filtered(config: Configuration) -> (AS56Mock, AS5600Driver<AS56Mock>) {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_config(config).unwrap();
+    mock.mock_set_filter_emulation(true);
+    (mock, sensor)
+}
+
+#[test]
+fn slow_filter_settles_per_the_datasheet_table() {
+    for filter in [
+        SlowFilter::X16,
+        SlowFilter::X8,
+        SlowFilter::X4,
+        SlowFilter::X2,
+    ] {
+        let (mock, mut sensor) = filtered(Configuration {
+            hysteresis: Hysteresis::Off,
+            slow_filter: filter,
+            fast_filter_threshold: FastFilterThreshold::SlowOnly,
+            ..Configuration::default()
+        });
+        mock.mock_set_raw_angle(100);
+        assert_eq!(sensor.read_angle().unwrap(), 0);
+
+        let settling = slow_filter_settling(filter);
+        mock.mock_advance(settling / 4);
+        let early = sensor.read_angle().unwrap();
+        assert!((50..90).contains(&early), "{filter:?}: {early}");
+        mock.mock_advance(settling - settling / 4);
+        let settled = sensor.read_angle().unwrap();
+        assert!((98..=100).contains(&settled), "{filter:?}: {settled}");
+    }
+}
+
+#[test]
+fn fast_filter_threshold_bypasses_the_slow_filter() {
+    let config = |fast_filter_threshold| Configuration {
+        hysteresis: Hysteresis::Off,
+        slow_filter: SlowFilter::X16,
+        fast_filter_threshold,
+        ..Configuration::default()
+    };
+
+    // A step above the threshold settles within the fast filter time.
+    let (mock, mut sensor) = filtered(config(FastFilterThreshold::Lsb6));
+    mock.mock_set_raw_angle(100);
+    mock.mock_advance(2 * SIM_STEP);
+    assert!((98..=100).contains(&sensor.read_angle().unwrap()));
+
+    // Once settled, a step below the threshold stays on the slow filter.
+    mock.mock_advance(slow_filter_settling(SlowFilter::X16));
+    assert_eq!(sensor.read_angle().unwrap(), 100);
+    mock.mock_set_raw_angle(105);
+    mock.mock_advance(2 * SIM_STEP);
+    assert!(sensor.read_angle().unwrap() < 104);
+
+    // Without a threshold the same step only settles with the slow filter.
+    let (mock, mut sensor) = filtered(config(FastFilterThreshold::SlowOnly));
+    mock.mock_set_raw_angle(100);
+    mock.mock_advance(2 * SIM_STEP);
+    assert!(sensor.read_angle().unwrap() < 50);
+    mock.mock_advance(slow_filter_settling(SlowFilter::X16));
+    assert!((98..=100).contains(&sensor.read_angle().unwrap()));
+}
+
+#[test]
+fn slow_filter_settling_matches_the_datasheet() {
+    assert_eq!(
+        slow_filter_settling(SlowFilter::X16),
+        Duration::from_micros(2200)
+    );
+    assert_eq!(
+        slow_filter_settling(SlowFilter::X8),
+        Duration::from_micros(1100)
+    );
+    assert_eq!(
+        slow_filter_settling(SlowFilter::X4),
+        Duration::from_micros(550)
+    );
+    assert_eq!(
+        slow_filter_settling(SlowFilter::X2),
+        Duration::from_micros(286)
+    );
+}
+
+// --- Power modes ---
+
+fn powered(config: Configuration) -> (AS56Mock, AS5600Driver<AS56Mock>) {
+    let mock = AS56Mock::new();
+    let mut sensor = AS5600Driver::new(mock.clone());
+    sensor.set_config(config).unwrap();
+    mock.mock_set_power_emulation(true);
+    (mock, sensor)
+}
+
+#[test]
+fn low_power_modes_update_once_per_polling_interval() {
+    for mode in [PowerMode::LPM1, PowerMode::LPM2, PowerMode::LPM3] {
+        let (mock, mut sensor) = powered(Configuration {
+            power_mode: mode,
+            watchdog: false,
+            ..Configuration::default()
+        });
+        mock.mock_set_shaft_angle(0.0);
+        mock.mock_set_speed_profile(SpeedProfile::Constant(60.0));
+
+        let mut updates = Vec::new();
+        let mut last = sensor.read_angle().unwrap();
+        while mock.mock_now() < Duration::from_millis(500) {
+            mock.mock_advance(SIM_STEP);
+            let angle = sensor.read_angle().unwrap();
+            if angle != last {
+                updates.push(mock.mock_now());
+                last = angle;
+            }
+        }
+
+        let interval = polling_interval(mode);
+        assert!(updates.len() >= 4, "{mode:?}: {updates:?}");
+        for pair in updates.windows(2) {
+            let period = pair[1] - pair[0];
+            assert!(
+                interval <= period && period < interval + SIM_STEP,
+                "{mode:?}: {period:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn watchdog_enters_lpm3_after_a_minute_within_the_band() {
+    let (mock, mut sensor) = powered(Configuration::default());
+    mock.mock_set_raw_angle(1000);
+    mock.mock_advance(SIM_STEP);
+
+    // Jitter within 4 LSB of the resting position is not movement.
+    let jitter = [4, -4, 2, -1, 0, 3, -3];
+    for offset in jitter.iter().cycle().take(59) {
+        mock.mock_set_raw_angle(1000u16.wrapping_add_signed(*offset));
+        mock.mock_advance(Duration::from_secs(1));
+        assert!(!mock.mock_watchdog_sleeping());
+    }
+    mock.mock_advance(Duration::from_millis(900));
+    assert!(!mock.mock_watchdog_sleeping());
+    mock.mock_advance(Duration::from_millis(200));
+    assert!(mock.mock_watchdog_sleeping());
+    assert_eq!(mock.mock_power_mode(), PowerMode::LPM3);
+    assert_eq!(sensor.get_config().unwrap().power_mode, PowerMode::Nominal);
+
+    // Moving further wakes the chip at the next LPM3 poll.
+    mock.mock_set_raw_angle(1010);
+    mock.mock_advance(polling_interval(PowerMode::LPM3) + SIM_STEP);
+    assert!(!mock.mock_watchdog_sleeping());
+    assert_eq!(mock.mock_power_mode(), PowerMode::Nominal);
+    assert_eq!(sensor.read_raw_angle().unwrap(), 1010);
+}
+
+#[test]
+fn movement_restarts_the_watchdog_timeout() {
+    let (mock, _sensor) = powered(Configuration::default());
+    mock.mock_advance(WATCHDOG_TIMEOUT / 2);
+    mock.mock_set_raw_angle(WATCHDOG_THRESHOLD_LSB + 1);
+    mock.mock_advance(WATCHDOG_TIMEOUT - SIM_STEP);
+    assert!(!mock.mock_watchdog_sleeping());
+    mock.mock_advance(2 * SIM_STEP);
+    assert!(mock.mock_watchdog_sleeping());
+
+    // Without the watchdog bit the chip never sleeps.
+    let (mock, _sensor) = powered(Configuration {
+        watchdog: false,
+        ..Configuration::default()
+    });
+    mock.mock_advance(2 * WATCHDOG_TIMEOUT);
+    assert!(!mock.mock_watchdog_sleeping());
+}
@@ -0,0 +1,250 @@
+use crate::error::AS56Error;
+use crate::traits::AS5600Interface;
+
+/// Number of raw counts in one full revolution (12-bit resolution).
+pub const COUNTS_PER_TURN: i64 = 4096;
+
+/// Half of a revolution in raw counts, the largest wrap detection threshold.
+pub const HALF_TURN: u16 = 2048;
+
+/// Quarter of a revolution in raw counts, the default wrap detection threshold.
+pub const DEFAULT_THRESHOLD: u16 = 1024;
+
+/// Returns the shortest signed step between two raw angle samples.
+///
+/// The result is in the range `-2048..=2047`, so a move from 4095 to 0 is `+1`.
+pub fn unwrap_delta(prev: u16, current: u16) -> i16 {
+    let delta = (current as i32 - prev as i32).rem_euclid(COUNTS_PER_TURN as i32);
+    if delta >= HALF_TURN as i32 {
+        (delta - COUNTS_PER_TURN as i32) as i16
+    } else {
+        delta as i16
+    }
+}
+
+/// Multi-turn position tracker built on top of [`AS5600Interface::read_raw_angle`].
+///
+/// Every call to [`update`](Self::update) samples the raw angle and detects
+/// 4095 → 0 (and 0 → 4095) wraps, accumulating the absolute position in a signed
+/// 64-bit count.
+///
+/// A raw difference of up to `threshold` counts between two samples is taken as
+/// a direct move, a difference of at least `4096 - threshold` counts as a wrap.
+/// Anything in between cannot be attributed to either direction: the sampling rate
+/// is too low for the shaft speed. Such steps are still accumulated along the shortest
+/// path, but are flagged (see [`last_step_ambiguous`](Self::last_step_ambiguous)).
+/// The default threshold of a quarter turn flags steps between 1024 and 3072 counts;
+/// a half-turn threshold accepts every step and disables the detection.
+pub struct MultiTurn<S> {
+    sensor: S,
+    threshold: u16,
+    last_raw: Option<u16>,
+    counts: i64,
+    preset: bool,
+    last_ambiguous: bool,
+    ambiguous_steps: u32,
+}
+
+impl<S: AS5600Interface> MultiTurn<S> {
+    /// Creates a tracker with the default quarter-turn wrap threshold.
+    pub fn new(sensor: S) -> Self {
+        Self::with_threshold(sensor, DEFAULT_THRESHOLD)
+    }
+
+    /// Creates a tracker with a custom wrap threshold in raw counts (clamped to `1..=2048`).
+    pub fn with_threshold(sensor: S, threshold: u16) -> Self {
+        Self {
+            sensor,
+            threshold: threshold.clamp(1, HALF_TURN),
+            last_raw: None,
+            counts: 0,
+            preset: false,
+            last_ambiguous: false,
+            ambiguous_steps: 0,
+        }
+    }
+
+    /// Samples the sensor and returns the updated total position in raw counts.
+    ///
+    /// Unless the count was preset, the first sample starts tracking in turn 0
+    /// at the current raw angle.
+    pub fn update(&mut self) -> Result<i64, AS56Error<S::Error>> {
+        let raw = self.sensor.read_raw_angle()?;
+        self.last_ambiguous = false;
+
+        match self.last_raw {
+            None if !self.preset => self.counts = raw as i64,
+            None => {}
+            Some(prev) => {
+                let diff = raw as i32 - prev as i32;
+                let threshold = self.threshold as i32;
+                let step = if diff.abs() <= threshold {
+                    diff
+                } else if diff.abs() >= COUNTS_PER_TURN as i32 - threshold {
+                    diff - diff.signum() * COUNTS_PER_TURN as i32
+                } else {
+                    self.last_ambiguous = true;
+                    self.ambiguous_steps = self.ambiguous_steps.saturating_add(1);
+                    unwrap_delta(prev, raw) as i32
+                };
+                self.counts += step as i64;
+            }
+        }
+
+        self.last_raw = Some(raw);
+        Ok(self.counts)
+    }
+
+    /// Returns the number of completed turns (rounded towards negative infinity).
+    pub fn turns(&self) -> i64 {
+        self.counts.div_euclid(COUNTS_PER_TURN)
+    }
+
+    /// Returns the total position in raw counts.
+    pub fn total_counts(&self) -> i64 {
+        self.counts
+    }
+
+    /// Returns the position within the current turn in raw counts (0..4095).
+    pub fn angle(&self) -> u16 {
+        self.counts.rem_euclid(COUNTS_PER_TURN) as u16
+    }
+
+    /// Returns the total position in degrees.
+    pub fn angle_degrees(&self) -> f64 {
+        self.counts as f64 * 360.0 / COUNTS_PER_TURN as f64
+    }
+
+    /// Sets the total position to `counts` at the current shaft position.
+    ///
+    /// If no sample has been taken yet, the next [`update`](Self::update) becomes
+    /// the reference point for the preset value.
+    pub fn preset(&mut self, counts: i64) {
+        self.counts = counts;
+        self.preset = true;
+    }
+
+    /// Resets the total position to zero at the current shaft position.
+    pub fn reset(&mut self) {
+        self.preset(0);
+    }
+
+    /// Returns `true` if the last sample moved too far to tell which way it wrapped.
+    pub fn last_step_ambiguous(&self) -> bool {
+        self.last_ambiguous
+    }
+
+    /// Returns how many ambiguous steps have been seen since the tracker was created.
+    pub fn ambiguous_steps(&self) -> u32 {
+        self.ambiguous_steps
+    }
+
+    /// Gives access to the underlying sensor.
+    pub fn sensor(&mut self) -> &mut S {
+        &mut self.sensor
+    }
+
+    /// Consumes the tracker and returns the underlying sensor.
+    pub fn release(self) -> S {
+        self.sensor
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::AS5600Driver;
+    use crate::mock::AS56Mock;
+    use std::vec::Vec;
+
+    type Tracker = MultiTurn<AS5600Driver<AS56Mock>>;
+
+    fn setup(threshold: Option<u16>) -> (AS56Mock, Tracker) {
+        let mock = AS56Mock::new();
+        let sensor = AS5600Driver::new(mock.clone());
+        let tracker = match threshold {
+            Some(threshold) => MultiTurn::with_threshold(sensor, threshold),
+            None => MultiTurn::new(sensor),
+        };
+        (mock, tracker)
+    }
+
+    /// Feeds the raw angles in `path` and returns the position after each one.
+    fn track(tracker: &mut Tracker, mock: &AS56Mock, path: &[u16]) -> Vec<i64> {
+        path.iter()
+            .map(|&raw| {
+                mock.mock_set_raw_angle(raw);
+                tracker.update().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wraps_are_counted_in_both_directions() {
+        let (mock, mut tracker) = setup(None);
+        let forward = track(&mut tracker, &mock, &[4000, 4095, 0, 100, 4090, 3100]);
+        assert_eq!(forward, [4000, 4095, 4096, 4196, 4090, 3100]);
+        let backward = track(&mut tracker, &mock, &[2100, 1100, 100, 4000]);
+        assert_eq!(backward, [2100, 1100, 100, -96]);
+        assert_eq!(tracker.turns(), -1);
+        assert_eq!(tracker.angle(), 4000);
+        assert_eq!(tracker.angle_degrees(), -96.0 * 360.0 / 4096.0);
+        assert_eq!(tracker.ambiguous_steps(), 0);
+    }
+
+    #[test]
+    fn ambiguous_steps_are_flagged() {
+        let (mock, mut tracker) = setup(None);
+        track(&mut tracker, &mock, &[0, 1024]);
+        assert!(!tracker.last_step_ambiguous());
+
+        // 1025 counts forward could also be 3071 backward: taken along the shortest path.
+        assert_eq!(track(&mut tracker, &mock, &[2049]), [2049]);
+        assert!(tracker.last_step_ambiguous());
+        assert_eq!(track(&mut tracker, &mock, &[3049]), [3049]);
+        assert!(!tracker.last_step_ambiguous());
+        // 2000 forward across the wrap, or 2096 backward.
+        assert_eq!(track(&mut tracker, &mock, &[953]), [5049]);
+        assert!(tracker.last_step_ambiguous());
+        assert_eq!(tracker.ambiguous_steps(), 2);
+    }
+
+    #[test]
+    fn preset_and_reset_keep_the_shaft_position() {
+        let (mock, mut tracker) = setup(None);
+        // Before the first sample, the preset value applies at the first sample.
+        tracker.preset(10 * 4096);
+        let positions = track(&mut tracker, &mock, &[1000, 1100]);
+        assert_eq!(positions, [10 * 4096, 10 * 4096 + 100]);
+
+        tracker.reset();
+        assert_eq!(tracker.total_counts(), 0);
+        assert_eq!(track(&mut tracker, &mock, &[1000]), [-100]);
+
+        tracker.preset(-5);
+        assert_eq!(track(&mut tracker, &mock, &[1010]), [5]);
+    }
+
+    #[test]
+    fn threshold_is_clamped() {
+        // A threshold of 0 becomes 1: only single counts and the 4095/0 wrap are clear.
+        let (mock, mut tracker) = setup(Some(0));
+        let positions = track(&mut tracker, &mock, &[0, 1, 0, 4095, 3]);
+        assert_eq!(positions, [0, 1, 0, -1, 3]);
+        assert_eq!(tracker.ambiguous_steps(), 1);
+
+        // Anything above a half turn becomes a half turn, which accepts every step.
+        let (mock, mut tracker) = setup(Some(u16::MAX));
+        let positions = track(&mut tracker, &mock, &[0, 2047, 0, 2048]);
+        assert_eq!(positions, [0, 2047, 0, 2048]);
+        assert_eq!(tracker.ambiguous_steps(), 0);
+    }
+
+    #[test]
+    fn unwrap_delta_takes_the_shortest_path() {
+        assert_eq!(unwrap_delta(4095, 0), 1);
+        assert_eq!(unwrap_delta(0, 4095), -1);
+        assert_eq!(unwrap_delta(0, 2047), 2047);
+        assert_eq!(unwrap_delta(0, 2048), -2048);
+    }
+}
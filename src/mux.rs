@@ -0,0 +1,232 @@
+use crate::driver::AS5600Driver;
+use core::cell::RefCell;
+use embedded_hal::i2c::{ErrorType, I2c, Operation, SevenBitAddress};
+
+/// Default I2C address of the TCA9548A/PCA9548 (A0..A2 tied low).
+pub const TCA9548A_DEFAULT_ADDR: u8 = 0x70;
+
+/// Number of downstream channels on the TCA9548A/PCA9548.
+pub const MUX_CHANNELS: u8 = 8;
+
+/// Bus state shared by all channels of one multiplexer.
+struct MuxBus<I2C> {
+    i2c: I2C,
+    address: u8,
+    selected: Option<u8>,
+}
+
+impl<I2C: I2c<SevenBitAddress>> MuxBus<I2C> {
+    /// Routes the bus to `channel`, writing the control register only if needed.
+    fn select(&mut self, channel: u8) -> Result<(), I2C::Error> {
+        if self.selected == Some(channel) {
+            return Ok(());
+        }
+        // The previous selection is unknown until the write succeeds.
+        self.selected = None;
+        self.i2c.write(self.address, &[1 << channel])?;
+        self.selected = Some(channel);
+        Ok(())
+    }
+}
+
+/// A TCA9548A/PCA9548 I2C multiplexer owning the upstream bus.
+///
+/// Hands out [`MuxChannel`]s that implement [`embedded_hal::i2c::I2c`], so several
+/// AS5600 sensors with the same fixed address can live on one bus. Each channel
+/// switches the multiplexer before its transaction, and only when the channel
+/// actually changes.
+///
+/// Channels borrow the multiplexer through a [`RefCell`], so they can be used from
+/// one thread (or one executor task) at a time.
+pub struct Tca9548a<I2C> {
+    bus: RefCell<MuxBus<I2C>>,
+}
+
+impl<I2C: I2c<SevenBitAddress>> Tca9548a<I2C> {
+    /// Creates a multiplexer at the default address (0x70).
+    pub fn new(i2c: I2C) -> Self {
+        Self::with_address(i2c, TCA9548A_DEFAULT_ADDR)
+    }
+
+    /// Creates a multiplexer at a custom address.
+    ///
+    /// The address is not checked; the TCA9548A address pins select 0x70..=0x77.
+    pub fn with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            bus: RefCell::new(MuxBus {
+                i2c,
+                address,
+                selected: None,
+            }),
+        }
+    }
+
+    /// Returns an I2C bus routed to `channel`, or `None` if `channel` is not 0..=7.
+    pub fn channel(&self, channel: u8) -> Option<MuxChannel<'_, I2C>> {
+        if channel >= MUX_CHANNELS {
+            return None;
+        }
+        Some(MuxChannel {
+            bus: &self.bus,
+            channel,
+        })
+    }
+
+    /// Creates one [`AS5600Driver`] per entry of `channels`, all sharing this bus.
+    ///
+    /// Returns `None` if any channel is not 0..=7.
+    pub fn drivers<const N: usize>(
+        &self,
+        channels: [u8; N],
+    ) -> Option<[AS5600Driver<MuxChannel<'_, I2C>>; N]> {
+        if channels.iter().any(|&ch| ch >= MUX_CHANNELS) {
+            return None;
+        }
+        Some(channels.map(|ch| {
+            AS5600Driver::new(MuxChannel {
+                bus: &self.bus,
+                channel: ch,
+            })
+        }))
+    }
+
+    /// Returns the channel the multiplexer is currently routed to, if known.
+    pub fn selected(&self) -> Option<u8> {
+        self.bus.borrow().selected
+    }
+
+    /// Disconnects all downstream channels.
+    pub fn disable_all(&self) -> Result<(), I2C::Error> {
+        let mut bus = self.bus.borrow_mut();
+        bus.selected = None;
+        let address = bus.address;
+        bus.i2c.write(address, &[0x00])
+    }
+
+    /// Consumes the multiplexer and returns the upstream bus.
+    pub fn release(self) -> I2C {
+        self.bus.into_inner().i2c
+    }
+}
+
+/// One downstream channel of a [`Tca9548a`].
+pub struct MuxChannel<'a, I2C> {
+    bus: &'a RefCell<MuxBus<I2C>>,
+    channel: u8,
+}
+
+impl<I2C> MuxChannel<'_, I2C> {
+    /// Returns the channel number (0..=7).
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+}
+
+impl<I2C: ErrorType> ErrorType for MuxChannel<'_, I2C> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c<SevenBitAddress>> I2c<SevenBitAddress> for MuxChannel<'_, I2C> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        let mut bus = self.bus.borrow_mut();
+        bus.select(self.channel)?;
+        bus.i2c.read(address, read)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        let mut bus = self.bus.borrow_mut();
+        bus.select(self.channel)?;
+        bus.i2c.write(address, write)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut bus = self.bus.borrow_mut();
+        bus.select(self.channel)?;
+        bus.i2c.write_read(address, write, read)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut bus = self.bus.borrow_mut();
+        bus.select(self.channel)?;
+        bus.i2c.transaction(address, operations)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::error::{AS56Error, ErrorKind};
+    use crate::mock::{AS56Mock, MockMux};
+    use crate::regs::DEFAULT_ADDR;
+    use crate::traits::AS5600Interface;
+
+    #[test]
+    fn selects_only_on_channel_change() {
+        let mock = MockMux::with_devices(2);
+        mock.mock_device(1).unwrap().mock_set_raw_angle(2000);
+        let mux = Tca9548a::new(mock.clone());
+        let [mut a, mut b] = mux.drivers([0, 1]).unwrap();
+
+        assert_eq!(a.read_raw_angle().unwrap(), 0);
+        a.read_angle().unwrap();
+        assert_eq!((mock.mock_select_count(), mock.mock_control()), (1, 0b01));
+
+        assert_eq!(b.read_raw_angle().unwrap(), 2000);
+        b.get_agc().unwrap();
+        assert_eq!((mock.mock_select_count(), mock.mock_control()), (2, 0b10));
+        assert_eq!(mux.selected(), Some(1));
+
+        a.read_raw_angle().unwrap();
+        assert_eq!(mock.mock_select_count(), 3);
+
+        // After disabling, the next transaction selects again even on the same channel.
+        mux.disable_all().unwrap();
+        assert_eq!((mock.mock_select_count(), mock.mock_control()), (4, 0));
+        a.read_raw_angle().unwrap();
+        assert_eq!((mock.mock_select_count(), mock.mock_control()), (5, 0b01));
+    }
+
+    #[test]
+    fn rejects_channels_out_of_range() {
+        let mux = Tca9548a::new(MockMux::new());
+        assert!(mux.channel(8).is_none());
+        assert!(mux.drivers([0, 8]).is_none());
+    }
+
+    #[test]
+    fn no_or_several_enabled_devices_do_not_respond() {
+        let mut mock = MockMux::with_devices(2);
+        let mut sensor = AS5600Driver::new(mock.clone());
+        let err = sensor.read_raw_angle().unwrap_err();
+        assert_eq!(err, AS56Error::DeviceNotResponding(DEFAULT_ADDR));
+        assert_eq!(err.kind(), ErrorKind::NotResponding);
+
+        mock.write(TCA9548A_DEFAULT_ADDR, &[0b11]).unwrap();
+        let err = sensor.read_raw_angle().unwrap_err();
+        assert_eq!(err, AS56Error::DeviceNotResponding(DEFAULT_ADDR));
+
+        // A channel without a device is as empty as a disabled one.
+        mock.write(TCA9548A_DEFAULT_ADDR, &[0b100]).unwrap();
+        assert_eq!(
+            sensor.read_raw_angle().unwrap_err(),
+            AS56Error::DeviceNotResponding(DEFAULT_ADDR)
+        );
+        mock.write(TCA9548A_DEFAULT_ADDR, &[0b10]).unwrap();
+        sensor.read_raw_angle().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "mux channel 8 out of range (0..=7)")]
+    fn mock_attach_rejects_channels_out_of_range() {
+        MockMux::new().mock_attach(8, AS56Mock::new());
+    }
+}
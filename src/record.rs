@@ -0,0 +1,796 @@
+//! Recording and replay of I2C sessions.
+//!
+//! [`Recorder`] wraps any [`I2c`] bus (for example a Linux `/dev/i2c-*` device)
+//! and writes every transaction, with its timestamp and the bytes the sensor
+//! returned, to an [`io::Write`] sink. [`Replay`] reads such a recording back
+//! and acts as the sensor, so a captured session can drive tests in CI.
+//!
+//! ## File format
+//! All integers marked *varint* are unsigned LEB128.
+//! - Header: the magic bytes `AS56REC` followed by the format version (1).
+//! - One record per transaction:
+//!   - time since the previous record in microseconds (varint),
+//!   - I2C address (u8),
+//!   - status (u8): 0 for success, otherwise the error kind (see below),
+//!   - number of operations (varint),
+//!   - per operation: tag (u8, 0 = write, 1 = read), length (varint) and the
+//!     data bytes. Reads of failed transactions carry no data.
+//!
+//! Error kinds: 1 bus, 2 arbitration loss, 3 NACK address, 4 NACK data,
+//! 5 NACK unknown, 6 overrun, 7 other.
+
+use core::fmt;
+use core::time::Duration;
+use embedded_hal::i2c::{
+    Error, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress,
+};
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::Instant;
+use std::vec;
+use std::vec::Vec;
+
+/// Magic bytes at the start of every recording.
+pub const MAGIC: &[u8; 7] = b"AS56REC";
+/// Version of the recording format written by [`Recorder`].
+pub const FORMAT_VERSION: u8 = 1;
+
+const TAG_WRITE: u8 = 0;
+const TAG_READ: u8 = 1;
+
+/// Longest operation stored in a recording. The whole register file is 256 bytes,
+/// so longer operations are refused by the writers and reported as corrupt input
+/// by [`read_recording`].
+const MAX_OP_LEN: usize = 1024;
+
+/// One operation of a recorded transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedOp {
+    /// Bytes sent to the device.
+    Write(Vec<u8>),
+    /// Bytes returned by the device (zeroes if the transaction failed).
+    Read(Vec<u8>),
+}
+
+/// One recorded I2C transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedTransaction {
+    /// Time since the recording started.
+    pub timestamp: Duration,
+    /// Address the transaction was sent to.
+    pub address: u8,
+    /// Operations in bus order.
+    pub ops: Vec<RecordedOp>,
+    /// Error the bus reported, if the transaction failed.
+    pub error: Option<ErrorKind>,
+}
+
+// --- Recording ---
+
+/// I2C bus wrapper that records every transaction to `W`.
+///
+/// Transactions are forwarded to the wrapped bus unchanged. Write errors of the
+/// sink cannot be reported through the I2C error type, so recording stops at the
+/// first one and it is returned by [`finish`](Self::finish). The same applies to an
+/// operation longer than 1024 bytes (e.g. traffic to another device on a shared
+/// bus), which the format does not accept.
+pub struct Recorder<I2C, W: Write> {
+    i2c: I2C,
+    writer: W,
+    start: Instant,
+    last: Duration,
+    recorded: u64,
+    io_error: Option<io::Error>,
+}
+
+impl<I2C: I2c<SevenBitAddress>, W: Write> Recorder<I2C, W> {
+    /// Starts a recording: writes the header to `writer` and starts the clock.
+    pub fn new(i2c: I2C, mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        Ok(Self {
+            i2c,
+            writer,
+            start: Instant::now(),
+            last: Duration::ZERO,
+            recorded: 0,
+            io_error: None,
+        })
+    }
+
+    /// Returns the number of transactions recorded so far.
+    pub fn recorded(&self) -> u64 {
+        self.recorded
+    }
+
+    /// Returns the error that stopped the recording, if any.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.io_error.as_ref()
+    }
+
+    /// Flushes the recording and returns the wrapped bus and the sink.
+    pub fn finish(mut self) -> io::Result<(I2C, W)> {
+        if let Some(e) = self.io_error.take() {
+            return Err(e);
+        }
+        self.writer.flush()?;
+        Ok((self.i2c, self.writer))
+    }
+
+    /// Appends one transaction to the recording.
+    fn record(&mut self, address: u8, ops: &[Operation<'_>], result: &Result<(), I2C::Error>) {
+        if self.io_error.is_some() {
+            return;
+        }
+        if let Err(e) = check_op_lens(ops.iter().map(|op| match op {
+            Operation::Write(bytes) => bytes.len(),
+            Operation::Read(buf) => buf.len(),
+        })) {
+            self.io_error = Some(e);
+            return;
+        }
+        let timestamp = self.start.elapsed();
+        let delta = timestamp.saturating_sub(self.last);
+        self.last = timestamp;
+
+        let mut record = Vec::new();
+        write_varint(&mut record, delta.as_micros() as u64);
+        record.push(address);
+        record.push(match result {
+            Ok(()) => 0,
+            Err(e) => error_code(e.kind()),
+        });
+        write_varint(&mut record, ops.len() as u64);
+        for op in ops {
+            match op {
+                Operation::Write(bytes) => {
+                    record.push(TAG_WRITE);
+                    write_varint(&mut record, bytes.len() as u64);
+                    record.extend_from_slice(bytes);
+                }
+                Operation::Read(buf) => {
+                    record.push(TAG_READ);
+                    write_varint(&mut record, buf.len() as u64);
+                    if result.is_ok() {
+                        record.extend_from_slice(buf);
+                    }
+                }
+            }
+        }
+
+        match self.writer.write_all(&record) {
+            Ok(()) => self.recorded += 1,
+            Err(e) => self.io_error = Some(e),
+        }
+    }
+}
+
+impl<I2C: ErrorType, W: Write> ErrorType for Recorder<I2C, W> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c<SevenBitAddress>, W: Write> I2c<SevenBitAddress> for Recorder<I2C, W> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        let result = self.i2c.read(address, read);
+        self.record(address, &[Operation::Read(read)], &result);
+        result
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        let result = self.i2c.write(address, write);
+        self.record(address, &[Operation::Write(write)], &result);
+        result
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let result = self.i2c.write_read(address, write, read);
+        self.record(
+            address,
+            &[Operation::Write(write), Operation::Read(read)],
+            &result,
+        );
+        result
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let result = self.i2c.transaction(address, operations);
+        self.record(address, operations, &result);
+        result
+    }
+}
+
+// --- Replay ---
+
+/// Errors returned by a [`Replay`] device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The transaction differs from the recorded one at this index.
+    Diverged(usize),
+    /// The code under test issued more transactions than were recorded.
+    Exhausted,
+    /// The recorded transaction failed on the bus with this error.
+    Recorded(ErrorKind),
+}
+
+impl Error for ReplayError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ReplayError::Recorded(kind) => *kind,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Diverged(index) => {
+                write!(f, "transaction differs from recording at #{}", index)
+            }
+            ReplayError::Exhausted => write!(f, "recording exhausted"),
+            ReplayError::Recorded(kind) => write!(f, "recorded bus error: {}", kind),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// A transaction that did not match the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Index of the recorded transaction that was expected.
+    pub index: usize,
+    /// The recorded transaction, or `None` if the recording was exhausted.
+    pub expected: Option<RecordedTransaction>,
+    /// The transaction issued by the code under test. Its reads carry zeroed
+    /// buffers of the requested length, its timestamp is the replay time.
+    pub actual: RecordedTransaction,
+}
+
+/// I2C device that serves a recorded session back.
+///
+/// Each transaction must match the next recorded one (address, operation kinds,
+/// written bytes and read lengths). Matching reads are answered with the recorded
+/// bytes and recorded bus errors are reproduced. A mismatch is returned as
+/// [`ReplayError::Diverged`] and kept in [`divergences`](Self::divergences); the
+/// replay then stays at the expected transaction.
+pub struct Replay {
+    transactions: Vec<RecordedTransaction>,
+    position: usize,
+    realtime: bool,
+    start: Option<Instant>,
+    divergences: Vec<Divergence>,
+}
+
+impl Replay {
+    /// Creates a replay of `transactions`.
+    pub fn new(transactions: Vec<RecordedTransaction>) -> Self {
+        Self {
+            transactions,
+            position: 0,
+            realtime: false,
+            start: None,
+            divergences: Vec::new(),
+        }
+    }
+
+    /// Reads a recording written by [`Recorder`].
+    pub fn from_reader(reader: impl Read) -> io::Result<Self> {
+        Ok(Self::new(read_recording(reader)?))
+    }
+
+    /// Makes every transaction wait until its recorded time (relative to the
+    /// first transaction) has passed, reproducing the original timing.
+    pub fn realtime(mut self, realtime: bool) -> Self {
+        self.realtime = realtime;
+        self
+    }
+
+    /// Returns the recorded transactions.
+    pub fn transactions(&self) -> &[RecordedTransaction] {
+        &self.transactions
+    }
+
+    /// Returns the number of recorded transactions not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.transactions.len() - self.position
+    }
+
+    /// Returns `true` once every recorded transaction has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the transactions that did not match the recording.
+    pub fn divergences(&self) -> &[Divergence] {
+        &self.divergences
+    }
+
+    /// Rewinds to the first transaction and clears the divergences.
+    pub fn rewind(&mut self) {
+        self.position = 0;
+        self.start = None;
+        self.divergences.clear();
+    }
+
+    /// Waits until the recorded time of transaction `index` in real-time mode.
+    fn wait_for(&mut self, index: usize) {
+        let first = self.transactions[0].timestamp;
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let due = self.transactions[index].timestamp.saturating_sub(first);
+        if let Some(remaining) = due.checked_sub(start.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+
+    /// Replays the next transaction against `operations`.
+    fn replay(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), ReplayError> {
+        let index = self.position;
+        let Some(expected) = self.transactions.get(index) else {
+            self.diverge(index, address, operations);
+            return Err(ReplayError::Exhausted);
+        };
+        if !matches_recording(expected, address, operations) {
+            self.diverge(index, address, operations);
+            return Err(ReplayError::Diverged(index));
+        }
+
+        if self.realtime {
+            self.wait_for(index);
+        }
+        let expected = &self.transactions[index];
+        self.position += 1;
+        if let Some(kind) = expected.error {
+            return Err(ReplayError::Recorded(kind));
+        }
+        for (op, recorded) in operations.iter_mut().zip(&expected.ops) {
+            if let (Operation::Read(buf), RecordedOp::Read(data)) = (op, recorded) {
+                buf.copy_from_slice(data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a transaction that did not match the recording.
+    fn diverge(&mut self, index: usize, address: u8, operations: &[Operation<'_>]) {
+        let timestamp = self.start.map_or(Duration::ZERO, |start| start.elapsed());
+        let ops = operations
+            .iter()
+            .map(|op| match op {
+                Operation::Write(bytes) => RecordedOp::Write(bytes.to_vec()),
+                Operation::Read(buf) => RecordedOp::Read(vec![0; buf.len()]),
+            })
+            .collect();
+        self.divergences.push(Divergence {
+            index,
+            expected: self.transactions.get(index).cloned(),
+            actual: RecordedTransaction {
+                timestamp,
+                address,
+                ops,
+                error: None,
+            },
+        });
+    }
+}
+
+/// Returns `true` if `operations` to `address` are the ones recorded in `expected`.
+fn matches_recording(
+    expected: &RecordedTransaction,
+    address: u8,
+    operations: &[Operation<'_>],
+) -> bool {
+    expected.address == address
+        && expected.ops.len() == operations.len()
+        && expected
+            .ops
+            .iter()
+            .zip(operations)
+            .all(|(recorded, op)| match (recorded, op) {
+                (RecordedOp::Write(data), Operation::Write(bytes)) => data.as_slice() == *bytes,
+                (RecordedOp::Read(data), Operation::Read(buf)) => data.len() == buf.len(),
+                _ => false,
+            })
+}
+
+impl ErrorType for Replay {
+    type Error = ReplayError;
+}
+
+impl I2c<SevenBitAddress> for Replay {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.replay(address, operations)
+    }
+}
+
+// --- Encoding ---
+
+/// Reads all transactions of a recording written by [`Recorder`].
+///
+/// Fails with [`io::ErrorKind::InvalidData`] on malformed input, including
+/// operations longer than 1024 bytes.
+pub fn read_recording(mut reader: impl Read) -> io::Result<Vec<RecordedTransaction>> {
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    if &header[..7] != MAGIC {
+        return Err(invalid_data("not an AS5600 recording"));
+    }
+    if header[7] != FORMAT_VERSION {
+        return Err(invalid_data("unsupported recording version"));
+    }
+
+    let mut transactions = Vec::new();
+    let mut timestamp = Duration::ZERO;
+    // A recording may end after any complete record.
+    while let Some(delta) = read_varint(&mut reader, true)? {
+        timestamp = timestamp
+            .checked_add(Duration::from_micros(delta))
+            .ok_or_else(|| invalid_data("timestamp overflows"))?;
+        let address = read_u8(&mut reader)?;
+        let status = read_u8(&mut reader)?;
+        let error = (status != 0).then(|| error_kind(status));
+        let count = read_len(&mut reader)?;
+        let mut ops = Vec::with_capacity(count.min(16));
+        for _ in 0..count {
+            let tag = read_u8(&mut reader)?;
+            let len = read_len(&mut reader)?;
+            if len > MAX_OP_LEN {
+                return Err(invalid_data("operation too long"));
+            }
+            let mut data = vec![0; len];
+            // Failed transactions carry no read data.
+            if tag != TAG_READ || error.is_none() {
+                reader.read_exact(&mut data)?;
+            }
+            ops.push(match tag {
+                TAG_WRITE => RecordedOp::Write(data),
+                TAG_READ => RecordedOp::Read(data),
+                _ => return Err(invalid_data("unknown operation tag")),
+            });
+        }
+        transactions.push(RecordedTransaction {
+            timestamp,
+            address,
+            ops,
+            error,
+        });
+    }
+    Ok(transactions)
+}
+
+/// Writes `transactions` in the recording format, e.g. after editing a capture.
+///
+/// Fails with [`io::ErrorKind::InvalidInput`] on operations longer than 1024 bytes.
+pub fn write_recording(
+    mut writer: impl Write,
+    transactions: &[RecordedTransaction],
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[FORMAT_VERSION])?;
+    let mut last = Duration::ZERO;
+    for t in transactions {
+        check_op_lens(t.ops.iter().map(|op| match op {
+            RecordedOp::Write(data) | RecordedOp::Read(data) => data.len(),
+        }))?;
+        let mut record = Vec::new();
+        write_varint(
+            &mut record,
+            t.timestamp.saturating_sub(last).as_micros() as u64,
+        );
+        last = t.timestamp;
+        record.push(t.address);
+        record.push(t.error.map_or(0, error_code));
+        write_varint(&mut record, t.ops.len() as u64);
+        for op in &t.ops {
+            let (tag, data) = match op {
+                RecordedOp::Write(data) => (TAG_WRITE, data),
+                RecordedOp::Read(data) => (TAG_READ, data),
+            };
+            record.push(tag);
+            write_varint(&mut record, data.len() as u64);
+            if tag != TAG_READ || t.error.is_none() {
+                record.extend_from_slice(data);
+            }
+        }
+        writer.write_all(&record)?;
+    }
+    writer.flush()
+}
+
+/// Refuses operations the format does not accept.
+fn check_op_lens(mut lens: impl Iterator<Item = usize>) -> io::Result<()> {
+    if lens.any(|len| len > MAX_OP_LEN) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "operation longer than 1024 bytes",
+        ));
+    }
+    Ok(())
+}
+
+fn error_code(kind: ErrorKind) -> u8 {
+    match kind {
+        ErrorKind::Bus => 1,
+        ErrorKind::ArbitrationLoss => 2,
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => 3,
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => 4,
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown) => 5,
+        ErrorKind::Overrun => 6,
+        _ => 7,
+    }
+}
+
+fn error_kind(code: u8) -> ErrorKind {
+    match code {
+        1 => ErrorKind::Bus,
+        2 => ErrorKind::ArbitrationLoss,
+        3 => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        4 => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        5 => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+        6 => ErrorKind::Overrun,
+        _ => ErrorKind::Other,
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reads a LEB128 varint. With `eof_ok`, a clean end of input before the first
+/// byte returns `None`.
+fn read_varint(reader: &mut impl Read, eof_ok: bool) -> io::Result<Option<u64>> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8];
+        let read = loop {
+            match reader.read(&mut byte) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result?,
+            }
+        };
+        if read == 0 {
+            if shift == 0 && eof_ok {
+                return Ok(None);
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        // The 10th byte only holds bit 63.
+        if shift == 63 && byte[0] > 1 {
+            return Err(invalid_data("varint overflows u64"));
+        }
+        value |= u64::from(byte[0] & 0x7F) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(invalid_data("varint too long"))
+}
+
+fn read_len(reader: &mut impl Read) -> io::Result<usize> {
+    let len = read_varint(reader, false)?.unwrap_or(0);
+    usize::try_from(len).map_err(|_| invalid_data("length out of range"))
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut byte = [0u8];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::AS56Mock;
+    use crate::traits::AS5600Interface;
+    use crate::{AS56Error, AS5600Driver};
+
+    /// The session recorded and replayed by the tests.
+    fn session<I2C: I2c>(i2c: I2C) -> (u16, u16, u8) {
+        let mut sensor = AS5600Driver::new(i2c);
+        sensor.set_zero_position(100).unwrap();
+        let raw = sensor.read_raw_angle().unwrap();
+        let angle = sensor.read_angle().unwrap();
+        let agc = sensor.get_agc().unwrap();
+        (raw, angle, agc)
+    }
+
+    fn record_session(mock: &AS56Mock) -> ((u16, u16, u8), Vec<u8>) {
+        let mut recorder = Recorder::new(mock.clone(), Vec::new()).unwrap();
+        let result = session(&mut recorder);
+        assert_eq!(recorder.recorded(), 4);
+        let (_, bytes) = recorder.finish().unwrap();
+        (result, bytes)
+    }
+
+    #[test]
+    fn replay_reproduces_a_recorded_session() {
+        let mock = AS56Mock::new();
+        mock.mock_set_raw_angle(1234);
+        let (live, bytes) = record_session(&mock);
+
+        let transactions = read_recording(&bytes[..]).unwrap();
+        assert_eq!(transactions.len(), 4);
+        let mut rewritten = Vec::new();
+        write_recording(&mut rewritten, &transactions).unwrap();
+        assert_eq!(rewritten, bytes);
+
+        let mut replay = Replay::new(transactions);
+        assert_eq!(session(&mut replay), live);
+        assert_eq!(live, (1234, 1134, 100));
+        assert!(replay.is_finished());
+        assert!(replay.divergences().is_empty());
+    }
+
+    #[test]
+    fn replay_reports_a_mismatched_transaction() {
+        let mock = AS56Mock::new();
+        let (_, bytes) = record_session(&mock);
+        let mut replay = Replay::from_reader(&bytes[..]).unwrap();
+
+        let mut sensor = AS5600Driver::new(&mut replay);
+        let err = sensor.set_zero_position(200).unwrap_err();
+        assert_eq!(err, AS56Error::I2c(ReplayError::Diverged(0)));
+        sensor.set_zero_position(100).unwrap();
+
+        assert_eq!(replay.remaining(), 3);
+        let divergence = &replay.divergences()[0];
+        assert_eq!(divergence.index, 0);
+        assert_eq!(
+            divergence.actual.ops,
+            [RecordedOp::Write(vec![0x01, 0x00, 0xC8])]
+        );
+        assert_eq!(
+            divergence.expected.as_ref().unwrap().ops,
+            [RecordedOp::Write(vec![0x01, 0x00, 0x64])]
+        );
+    }
+
+    fn header() -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        bytes
+    }
+
+    #[test]
+    fn rejects_oversized_operations() {
+        let mut bytes = header();
+        // delta, address, status, one operation: a write of 2000 bytes.
+        bytes.extend_from_slice(&[0, 0x36, 0, 1, TAG_WRITE]);
+        write_varint(&mut bytes, 2000);
+        let err = read_recording(&bytes[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn writers_refuse_oversized_operations() {
+        let mut mock = AS56Mock::new();
+        let mut recorder = Recorder::new(&mut mock, Vec::new()).unwrap();
+        recorder.read(0x36, &mut [0; MAX_OP_LEN]).unwrap();
+        recorder.write(0x36, &[0; MAX_OP_LEN + 1]).unwrap();
+        recorder.read(0x36, &mut [0; 2]).unwrap();
+        assert_eq!(recorder.recorded(), 1);
+        let err = recorder.finish().err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let transaction = |len| RecordedTransaction {
+            timestamp: Duration::from_micros(5),
+            address: 0x70,
+            ops: vec![RecordedOp::Write(vec![0xA5; len])],
+            error: None,
+        };
+        let mut bytes = Vec::new();
+        write_recording(&mut bytes, &[transaction(MAX_OP_LEN)]).unwrap();
+        assert_eq!(
+            read_recording(&bytes[..]).unwrap(),
+            [transaction(MAX_OP_LEN)]
+        );
+        let err = write_recording(Vec::new(), &[transaction(MAX_OP_LEN + 1)]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    /// Repeats one record after the header forever.
+    struct Repeating {
+        header: Vec<u8>,
+        record: Vec<u8>,
+        position: usize,
+    }
+
+    impl Read for Repeating {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let source = match self.header.len().checked_sub(self.position) {
+                Some(left) if left > 0 => &self.header[self.position..],
+                _ => {
+                    let offset = (self.position - self.header.len()) % self.record.len();
+                    &self.record[offset..]
+                }
+            };
+            let len = source.len().min(buf.len());
+            buf[..len].copy_from_slice(&source[..len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn rejects_overflowing_timestamps() {
+        // Each record adds u64::MAX µs, which overflows Duration after about a million.
+        let mut record = Vec::new();
+        write_varint(&mut record, u64::MAX);
+        record.extend_from_slice(&[0x36, 0, 0]);
+        let reader = Repeating {
+            header: header(),
+            record,
+            position: 0,
+        };
+        let err = read_recording(reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_overflowing_varints() {
+        let mut bytes = header();
+        write_varint(&mut bytes, u64::MAX);
+        assert_eq!(bytes.len(), 8 + 10);
+        bytes.extend_from_slice(&[0x36, 0, 0]);
+        assert_eq!(
+            read_recording(&bytes[..]).unwrap()[0].timestamp.as_micros(),
+            u64::MAX as u128
+        );
+
+        let mut bytes = header();
+        bytes.extend_from_slice(&[0xFF; 9]);
+        bytes.push(0x02);
+        let err = read_recording(&bytes[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// Returns `Interrupted` before every successful read.
+    struct Interrupting<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Interrupting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn retries_interrupted_reads() {
+        let (_, bytes) = record_session(&AS56Mock::new());
+        let reader = Interrupting {
+            data: &bytes,
+            interrupt: false,
+        };
+        assert_eq!(
+            read_recording(reader).unwrap(),
+            read_recording(&bytes[..]).unwrap()
+        );
+    }
+}
@@ -1,9 +1,41 @@
 /// Standard I2C address for the AS5600 (fixed by manufacturer).
 pub const DEFAULT_ADDR: u8 = 0x36;
 
+/// Factory default I2C address for the AS5600L (programmable).
+pub const AS5600L_DEFAULT_ADDR: u8 = 0x40;
+
+/// Returns the datasheet name of a register, or `None` for unnamed addresses.
+pub fn register_name(reg: u8) -> Option<&'static str> {
+    let name = match reg {
+        regs::ZMCO => "ZMCO",
+        regs::ZPOS_HI => "ZPOS_HI",
+        regs::ZPOS_LO => "ZPOS_LO",
+        regs::MPOS_HI => "MPOS_HI",
+        regs::MPOS_LO => "MPOS_LO",
+        regs::MANG_HI => "MANG_HI",
+        regs::MANG_LO => "MANG_LO",
+        regs::CONF_HI => "CONF_HI",
+        regs::CONF_LO => "CONF_LO",
+        regs::STATUS => "STATUS",
+        regs::RAW_ANGLE_HI => "RAW_ANGLE_HI",
+        regs::RAW_ANGLE_LO => "RAW_ANGLE_LO",
+        regs::ANGLE_HI => "ANGLE_HI",
+        regs::ANGLE_LO => "ANGLE_LO",
+        regs::AGC => "AGC",
+        regs::MAGNITUDE_HI => "MAGNITUDE_HI",
+        regs::MAGNITUDE_LO => "MAGNITUDE_LO",
+        regs::I2C_ADDR => "I2C_ADDR",
+        regs::I2C_UPDT => "I2C_UPDT",
+        regs::BURN => "BURN",
+        _ => return None,
+    };
+    Some(name)
+}
+
 /// Register map for the AS5600 according to ams datasheet.
 ///
 /// Registers are mostly 12-bit values spread across two 8-bit registers (HI/LO).
+#[allow(clippy::module_inception)]
 pub mod regs {
     /// Zero setting multi-cycle counter.
     ///
@@ -59,6 +91,14 @@ pub mod regs {
     /// Magnitude - LO register.
     pub const MAGNITUDE_LO: u8 = 0x1C;
 
+    /// I2C address register (AS5600L only).
+    /// Bits 7:1 hold the 7-bit address; burned together with MANG and CONF by the 0x40 burn.
+    pub const I2C_ADDR: u8 = 0x20;
+
+    /// I2C address update register (AS5600L only).
+    /// Writing the new address (bits 7:1) here makes the chip respond at it immediately.
+    pub const I2C_UPDT: u8 = 0x21;
+
     /// Programming register.
     /// Used for `BURN_SETTINGS` (0x80) and `BURN_ANGLE` (0x40).
     pub const BURN: u8 = 0xFF;
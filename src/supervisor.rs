@@ -0,0 +1,414 @@
+use crate::error::AS56Error;
+use crate::traits::AS5600Interface;
+use crate::types::*;
+
+/// Values of the volatile programming registers; `None` means "not set".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Settings {
+    /// Zero position (ZPOS).
+    pub zpos: Option<u16>,
+    /// Maximum position (MPOS).
+    pub mpos: Option<u16>,
+    /// Maximum angle (MANG).
+    pub mang: Option<u16>,
+    /// CONF register.
+    pub config: Option<Configuration>,
+}
+
+impl Settings {
+    /// Power-on values of a chip whose OTP was never burned: all registers zero.
+    ///
+    /// Any other power-on state needs its own image. [`AS56Mock`](crate::mock::AS56Mock)
+    /// powers on with the watchdog enabled (CONF = 0x2000), so against the mock, as with
+    /// a chip whose OTP was burned, use
+    /// [`Supervisor::capture_reset_image`] or [`Supervisor::with_reset_image`].
+    pub const FACTORY: Self = Self {
+        zpos: Some(0),
+        mpos: Some(0),
+        mang: Some(0),
+        config: Some(Configuration {
+            power_mode: PowerMode::Nominal,
+            hysteresis: Hysteresis::Off,
+            output_stage: OutputStage::AnalogFull,
+            pwm_frequency: PwmFrequency::Hz115,
+            slow_filter: SlowFilter::X16,
+            fast_filter_threshold: FastFilterThreshold::SlowOnly,
+            watchdog: false,
+            unused_bits: 0,
+        }),
+    };
+}
+
+/// Result of a [`Supervisor::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The chip holds the desired settings.
+    Consistent,
+    /// Every deviating register was back at its power-on value: the chip was reset
+    /// (e.g. a brownout). The settings were re-applied.
+    Reset,
+    /// Some registers differed from the desired settings without the reset signature,
+    /// e.g. after a write by another bus controller. The settings were re-applied.
+    Drift,
+}
+
+/// Keeps the volatile settings of a sensor in place across chip resets.
+///
+/// ZPOS, MPOS, MANG and CONF written with the `set_*` methods are lost when the
+/// sensor loses power. The supervisor remembers every setting written through it
+/// (or given with [`set_desired`](Self::set_desired)), and [`check`](Self::check)
+/// compares them with the chip, re-applying them if they differ.
+///
+/// A mismatch is classified as a [`Verdict::Reset`] if each deviating register holds
+/// its power-on value, taken from the reset image ([`Settings::FACTORY`] by default;
+/// set the burned OTP values with [`with_reset_image`](Self::with_reset_image) or
+/// [`capture_reset_image`](Self::capture_reset_image)).
+/// If the image does not match the chip's real power-on values, a reset is reported
+/// as [`Verdict::Drift`]; the settings are re-applied either way.
+/// A setting equal to its power-on value cannot reveal a reset.
+///
+/// ```rust,ignore
+/// let mut encoder = Supervisor::new(AS5600Driver::new(i2c)).with_interval(100_000);
+/// encoder.capture_reset_image()?;
+/// encoder.set_max_angle(2048)?;
+/// loop {
+///     if let Some(Verdict::Reset) = encoder.poll(micros())? {
+///         log::warn!("sensor reset #{}", encoder.reset_count());
+///     }
+///     let angle = encoder.read_angle()?;
+/// }
+/// ```
+pub struct Supervisor<S> {
+    sensor: S,
+    desired: Settings,
+    reset_image: Settings,
+    interval: u64,
+    last_check: Option<u64>,
+    resets: u32,
+    drifts: u32,
+}
+
+impl<S: AS5600Interface> Supervisor<S> {
+    /// Wraps a sensor with no desired settings yet.
+    pub fn new(sensor: S) -> Self {
+        Self {
+            sensor,
+            desired: Settings::default(),
+            reset_image: Settings::FACTORY,
+            interval: 0,
+            last_check: None,
+            resets: 0,
+            drifts: 0,
+        }
+    }
+
+    /// Sets the power-on register values, e.g. the values burned to the OTP.
+    ///
+    /// Registers left `None` are unknown and never match the reset signature.
+    pub fn with_reset_image(mut self, image: Settings) -> Self {
+        self.reset_image = image;
+        self
+    }
+
+    /// Reads the current register values as the reset image.
+    ///
+    /// Call it at start-up, while the chip still holds its power-on values, before
+    /// writing any setting.
+    pub fn capture_reset_image(&mut self) -> Result<(), AS56Error<S::Error>> {
+        self.reset_image = Settings {
+            zpos: Some(self.sensor.get_zero_position()?),
+            mpos: Some(self.sensor.get_max_position()?),
+            mang: Some(self.sensor.get_max_angle()?),
+            config: Some(self.sensor.get_config()?),
+        };
+        Ok(())
+    }
+
+    /// Sets the minimum time between two checks run by [`poll`](Self::poll), in clock ticks.
+    pub fn with_interval(mut self, ticks: u64) -> Self {
+        self.interval = ticks;
+        self
+    }
+
+    /// Returns the settings the supervisor keeps on the chip.
+    pub fn desired(&self) -> Settings {
+        self.desired
+    }
+
+    /// Replaces the settings the supervisor keeps on the chip.
+    ///
+    /// Nothing is written until the next [`check`](Self::check).
+    pub fn set_desired(&mut self, settings: Settings) {
+        self.desired = settings;
+    }
+
+    /// Returns the number of resets detected so far.
+    pub fn reset_count(&self) -> u32 {
+        self.resets
+    }
+
+    /// Returns the number of mismatches without the reset signature detected so far.
+    pub fn drift_count(&self) -> u32 {
+        self.drifts
+    }
+
+    /// Runs [`check`](Self::check) if the interval elapsed since the last check.
+    ///
+    /// `timestamp` comes from any monotonic clock, in the ticks of
+    /// [`with_interval`](Self::with_interval). Returns `None` if no check was due.
+    /// A failed check does not count, so the next poll checks again.
+    pub fn poll(&mut self, timestamp: u64) -> Result<Option<Verdict>, AS56Error<S::Error>> {
+        if self
+            .last_check
+            .is_some_and(|last| timestamp.wrapping_sub(last) < self.interval)
+        {
+            return Ok(None);
+        }
+        let verdict = self.check()?;
+        self.last_check = Some(timestamp);
+        Ok(Some(verdict))
+    }
+
+    /// Compares the desired settings with the chip and re-applies deviating ones.
+    ///
+    /// Reads only the registers that have a desired value.
+    pub fn check(&mut self) -> Result<Verdict, AS56Error<S::Error>> {
+        let desired = self.desired;
+        let image = self.reset_image;
+        let mut reset = true;
+
+        let zpos = match desired.zpos {
+            Some(v) => deviates(self.sensor.get_zero_position()?, v, image.zpos, &mut reset),
+            None => false,
+        };
+        let mpos = match desired.mpos {
+            Some(v) => deviates(self.sensor.get_max_position()?, v, image.mpos, &mut reset),
+            None => false,
+        };
+        let mang = match desired.mang {
+            Some(v) => deviates(self.sensor.get_max_angle()?, v, image.mang, &mut reset),
+            None => false,
+        };
+        let config = match desired.config {
+            Some(v) => deviates(self.sensor.get_config()?, v, image.config, &mut reset),
+            None => false,
+        };
+
+        if !(zpos || mpos || mang || config) {
+            return Ok(Verdict::Consistent);
+        }
+        if let (true, Some(v)) = (zpos, desired.zpos) {
+            self.sensor.set_zero_position(v)?;
+        }
+        if let (true, Some(v)) = (mpos, desired.mpos) {
+            self.sensor.set_max_position(v)?;
+        }
+        if let (true, Some(v)) = (mang, desired.mang) {
+            self.sensor.set_max_angle(v)?;
+        }
+        if let (true, Some(v)) = (config, desired.config) {
+            self.sensor.set_config(v)?;
+        }
+
+        if reset {
+            self.resets += 1;
+            Ok(Verdict::Reset)
+        } else {
+            self.drifts += 1;
+            Ok(Verdict::Drift)
+        }
+    }
+
+    /// Returns a mutable reference to the underlying sensor.
+    ///
+    /// Settings written through it are not remembered.
+    pub fn sensor(&mut self) -> &mut S {
+        &mut self.sensor
+    }
+
+    /// Consumes the supervisor and returns the underlying sensor.
+    pub fn release(self) -> S {
+        self.sensor
+    }
+}
+
+/// Returns `true` if a register differs from its desired value.
+///
+/// Clears `reset` if it differs and does not hold its power-on value either.
+fn deviates<T: PartialEq>(actual: T, desired: T, power_on: Option<T>, reset: &mut bool) -> bool {
+    let differs = actual != desired;
+    if differs && power_on != Some(actual) {
+        *reset = false;
+    }
+    differs
+}
+
+impl<S: AS5600Interface> AS5600Interface for Supervisor<S> {
+    type Error = S::Error;
+
+    fn read_raw_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.read_raw_angle()
+    }
+
+    fn read_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.read_angle()
+    }
+
+    fn get_magnet_status(&mut self) -> Result<MagnetStatus, AS56Error<Self::Error>> {
+        self.sensor.get_magnet_status()
+    }
+
+    fn get_status_raw(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_status_raw()
+    }
+
+    fn get_magnitude(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.get_magnitude()
+    }
+
+    fn get_agc(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_agc()
+    }
+
+    fn get_burn_count(&mut self) -> Result<u8, AS56Error<Self::Error>> {
+        self.sensor.get_burn_count()
+    }
+
+    fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        self.sensor.read_snapshot()
+    }
+
+    fn get_config(&mut self) -> Result<Configuration, AS56Error<Self::Error>> {
+        self.sensor.get_config()
+    }
+
+    fn set_config(&mut self, config: Configuration) -> Result<(), AS56Error<Self::Error>> {
+        self.sensor.set_config(config)?;
+        self.desired.config = Some(config);
+        Ok(())
+    }
+
+    fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>> {
+        let mut applied = None;
+        self.sensor.modify_config(|c| {
+            f(c);
+            applied = Some(*c);
+        })?;
+        self.desired.config = applied;
+        Ok(())
+    }
+
+    fn get_zero_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.get_zero_position()
+    }
+
+    fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.sensor.set_zero_position(angle)?;
+        self.desired.zpos = Some(angle);
+        Ok(())
+    }
+
+    fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.get_max_position()
+    }
+
+    fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.sensor.set_max_position(angle)?;
+        self.desired.mpos = Some(angle);
+        Ok(())
+    }
+
+    fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>> {
+        self.sensor.get_max_angle()
+    }
+
+    fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>> {
+        self.sensor.set_max_angle(angle)?;
+        self.desired.mang = Some(angle);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::AS5600Driver;
+    use crate::mock::{AS56Mock, FaultAction};
+
+    fn supervised() -> (AS56Mock, Supervisor<AS5600Driver<AS56Mock>>) {
+        let mock = AS56Mock::new();
+        let mut supervisor = Supervisor::new(AS5600Driver::new(mock.clone()));
+        supervisor.capture_reset_image().unwrap();
+        supervisor.set_zero_position(100).unwrap();
+        supervisor.set_max_angle(2048).unwrap();
+        supervisor.set_power_mode(PowerMode::LPM2).unwrap();
+        (mock, supervisor)
+    }
+
+    #[test]
+    fn power_cycle_is_detected_and_settings_reapplied() {
+        let (mock, mut supervisor) = supervised();
+        assert_eq!(supervisor.check().unwrap(), Verdict::Consistent);
+
+        mock.mock_power_cycle();
+        let sensor = supervisor.sensor();
+        assert_eq!(sensor.get_zero_position().unwrap(), 0);
+        assert_eq!(sensor.get_config().unwrap().power_mode, PowerMode::Nominal);
+
+        assert_eq!(supervisor.check().unwrap(), Verdict::Reset);
+        assert_eq!((supervisor.reset_count(), supervisor.drift_count()), (1, 0));
+        assert_eq!(supervisor.get_zero_position().unwrap(), 100);
+        assert_eq!(supervisor.get_max_angle().unwrap(), 2048);
+        let config = supervisor.get_config().unwrap();
+        assert_eq!(config.power_mode, PowerMode::LPM2);
+        assert!(config.watchdog);
+        assert_eq!(supervisor.check().unwrap(), Verdict::Consistent);
+    }
+
+    #[test]
+    fn foreign_write_is_drift() {
+        let (_mock, mut supervisor) = supervised();
+        supervisor.sensor().set_zero_position(7).unwrap();
+        assert_eq!(supervisor.check().unwrap(), Verdict::Drift);
+        assert_eq!((supervisor.reset_count(), supervisor.drift_count()), (0, 1));
+        assert_eq!(supervisor.get_zero_position().unwrap(), 100);
+    }
+
+    #[test]
+    fn factory_image_does_not_match_the_mock() {
+        // The mock powers on with the watchdog enabled, unlike a blank chip.
+        let mock = AS56Mock::new();
+        let mut supervisor = Supervisor::new(AS5600Driver::new(mock.clone()));
+        supervisor.set_hysteresis(Hysteresis::Lsb2).unwrap();
+        mock.mock_power_cycle();
+        assert_eq!(supervisor.check().unwrap(), Verdict::Drift);
+        assert_eq!(
+            supervisor.get_config().unwrap().hysteresis,
+            Hysteresis::Lsb2
+        );
+    }
+
+    #[test]
+    fn poll_checks_once_per_interval() {
+        let (mock, supervisor) = supervised();
+        let mut supervisor = supervisor.with_interval(100);
+        assert_eq!(supervisor.poll(1_000).unwrap(), Some(Verdict::Consistent));
+        mock.mock_power_cycle();
+        assert_eq!(supervisor.poll(1_099).unwrap(), None);
+        assert_eq!(supervisor.poll(1_100).unwrap(), Some(Verdict::Reset));
+    }
+
+    #[test]
+    fn failed_poll_is_retried_at_once() {
+        let (mock, supervisor) = supervised();
+        let mut supervisor = supervisor.with_interval(100);
+        mock.mock_power_cycle();
+        mock.mock_fail_nth(1, FaultAction::BUS_ERROR);
+        assert!(supervisor.poll(1_000).is_err());
+        assert_eq!(supervisor.poll(1_001).unwrap(), Some(Verdict::Reset));
+        assert_eq!(supervisor.poll(1_002).unwrap(), None);
+    }
+}
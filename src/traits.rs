@@ -1,5 +1,7 @@
-use crate::types::*;
+use crate::driver::check_status;
 use crate::error::AS56Error;
+use crate::types::*;
+use crate::units::{Angle, RawCounts};
 
 /// A common interface for any AS5600-compatible sensor (real or simulated).
 ///
@@ -40,17 +42,356 @@ pub trait AS5600Interface {
     fn get_zero_position(&mut self) -> Result<u16, AS56Error<Self::Error>>;
 
     /// Sets the zero position (ZPOS) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
     fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
 
     /// Gets the current maximum position (MPOS).
     fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>>;
 
     /// Sets the maximum position (MPOS) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
     fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
 
     /// Gets the current maximum angle (MANG).
     fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>>;
 
     /// Sets the maximum angle (MANG) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
     fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
+
+    /// Sets the zero position (ZPOS) from typed [`RawCounts`].
+    fn set_zero_position_counts(
+        &mut self,
+        position: RawCounts,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.set_zero_position(position.counts())
+    }
+
+    /// Sets the maximum position (MPOS) from typed [`RawCounts`].
+    fn set_max_position_counts(
+        &mut self,
+        position: RawCounts,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.set_max_position(position.counts())
+    }
+
+    /// Sets the maximum angle (MANG) from typed [`RawCounts`].
+    fn set_max_angle_counts(&mut self, span: RawCounts) -> Result<(), AS56Error<Self::Error>> {
+        self.set_max_angle(span.counts())
+    }
+
+    /// Reads the raw angle as typed [`RawCounts`].
+    fn read_raw_counts(&mut self) -> Result<RawCounts, AS56Error<Self::Error>> {
+        Ok(RawCounts::from_masked(self.read_raw_angle()?))
+    }
+
+    /// Reads the scaled output angle as a typed [`Angle`].
+    fn read_scaled_angle(&mut self) -> Result<Angle, AS56Error<Self::Error>> {
+        Ok(Angle::from_masked(self.read_angle()?))
+    }
+
+    /// Reads all live registers (status, both angles, AGC and magnitude) at once.
+    ///
+    /// The default implementation reads them one by one; drivers override it
+    /// with a burst read.
+    fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        let status_raw = self.get_status_raw()?;
+        Ok(Snapshot {
+            status: check_status(status_raw)?,
+            status_raw,
+            raw_angle: self.read_raw_angle()?,
+            angle: self.read_angle()?,
+            agc: self.get_agc()?,
+            magnitude: self.get_magnitude()?,
+        })
+    }
+
+    /// Changes the configuration through a closure and writes CONF only if it changed.
+    ///
+    /// The default implementation uses `get_config` and `set_config`; drivers
+    /// override it with a single read and a single two-byte write that keep the
+    /// unused CONF bits. Generic over the closure, so it is not available on
+    /// `dyn AS5600Interface`.
+    fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        let current = self.get_config()?;
+        let mut config = current;
+        f(&mut config);
+        if config != current {
+            self.set_config(config)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the power mode, keeping the other configuration fields.
+    fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.power_mode = mode)
+    }
+
+    /// Sets the hysteresis, keeping the other configuration fields.
+    fn set_hysteresis(&mut self, hysteresis: Hysteresis) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.hysteresis = hysteresis)
+    }
+
+    /// Sets the output stage, keeping the other configuration fields.
+    fn set_output_stage(&mut self, stage: OutputStage) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.output_stage = stage)
+    }
+
+    /// Sets the PWM frequency, keeping the other configuration fields.
+    fn set_pwm_frequency(&mut self, frequency: PwmFrequency) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.pwm_frequency = frequency)
+    }
+
+    /// Sets the slow filter, keeping the other configuration fields.
+    fn set_slow_filter(&mut self, filter: SlowFilter) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.slow_filter = filter)
+    }
+
+    /// Sets the fast filter threshold, keeping the other configuration fields.
+    fn set_fast_filter_threshold(
+        &mut self,
+        threshold: FastFilterThreshold,
+    ) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.fast_filter_threshold = threshold)
+    }
+
+    /// Enables or disables the watchdog, keeping the other configuration fields.
+    fn set_watchdog(&mut self, enabled: bool) -> Result<(), AS56Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        self.modify_config(|c| c.watchdog = enabled)
+    }
+}
+
+/// Async counterpart of [`AS5600Interface`] for `embedded-hal-async` based drivers.
+///
+/// Every method has the same meaning as its blocking equivalent, but returns a future
+/// so it can be used from async executors such as Embassy.
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AS5600InterfaceAsync {
+    /// The error type returned by the sensor methods.
+    type Error;
+
+    /// Reads the raw 12-bit angle from the Hall sensors.
+    async fn read_raw_angle(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Reads the 12-bit angle after applying all settings.
+    async fn read_angle(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Returns the current magnet status and field strength health.
+    async fn get_magnet_status(&mut self) -> Result<MagnetStatus, AS56Error<Self::Error>>;
+
+    /// Returns the raw value of the status register.
+    async fn get_status_raw(&mut self) -> Result<u8, AS56Error<Self::Error>>;
+
+    /// Returns the magnitude value from the Hall sensors.
+    async fn get_magnitude(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Returns the current Automatic Gain Control (AGC) value.
+    async fn get_agc(&mut self) -> Result<u8, AS56Error<Self::Error>>;
+
+    /// Returns the number of times the settings have been permanently burned to the chip.
+    async fn get_burn_count(&mut self) -> Result<u8, AS56Error<Self::Error>>;
+
+    /// Reads the current full configuration from the chip.
+    async fn get_config(&mut self) -> Result<Configuration, AS56Error<Self::Error>>;
+
+    /// Writes a new configuration to the chip's volatile memory.
+    async fn set_config(&mut self, config: Configuration) -> Result<(), AS56Error<Self::Error>>;
+
+    /// Gets the current zero position (ZPOS).
+    async fn get_zero_position(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Sets the zero position (ZPOS) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
+    async fn set_zero_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
+
+    /// Gets the current maximum position (MPOS).
+    async fn get_max_position(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Sets the maximum position (MPOS) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
+    async fn set_max_position(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
+
+    /// Gets the current maximum angle (MANG).
+    async fn get_max_angle(&mut self) -> Result<u16, AS56Error<Self::Error>>;
+
+    /// Sets the maximum angle (MANG) in volatile memory.
+    ///
+    /// Values above 4095 are rejected with [`AS56Error::OutOfRange`].
+    async fn set_max_angle(&mut self, angle: u16) -> Result<(), AS56Error<Self::Error>>;
+
+    /// Sets the zero position (ZPOS) from typed [`RawCounts`].
+    async fn set_zero_position_counts(
+        &mut self,
+        position: RawCounts,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.set_zero_position(position.counts()).await
+    }
+
+    /// Sets the maximum position (MPOS) from typed [`RawCounts`].
+    async fn set_max_position_counts(
+        &mut self,
+        position: RawCounts,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.set_max_position(position.counts()).await
+    }
+
+    /// Sets the maximum angle (MANG) from typed [`RawCounts`].
+    async fn set_max_angle_counts(
+        &mut self,
+        span: RawCounts,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.set_max_angle(span.counts()).await
+    }
+
+    /// Reads the raw angle as typed [`RawCounts`].
+    async fn read_raw_counts(&mut self) -> Result<RawCounts, AS56Error<Self::Error>> {
+        Ok(RawCounts::from_masked(self.read_raw_angle().await?))
+    }
+
+    /// Reads the scaled output angle as a typed [`Angle`].
+    async fn read_scaled_angle(&mut self) -> Result<Angle, AS56Error<Self::Error>> {
+        Ok(Angle::from_masked(self.read_angle().await?))
+    }
+
+    /// Reads all live registers (status, both angles, AGC and magnitude) at once.
+    ///
+    /// The default implementation reads them one by one; drivers override it
+    /// with a burst read.
+    async fn read_snapshot(&mut self) -> Result<Snapshot, AS56Error<Self::Error>> {
+        let status_raw = self.get_status_raw().await?;
+        Ok(Snapshot {
+            status: check_status(status_raw)?,
+            status_raw,
+            raw_angle: self.read_raw_angle().await?,
+            angle: self.read_angle().await?,
+            agc: self.get_agc().await?,
+            magnitude: self.get_magnitude().await?,
+        })
+    }
+
+    /// Changes the configuration through a closure and writes CONF only if it changed.
+    ///
+    /// The default implementation uses `get_config` and `set_config`; drivers
+    /// override it with a single read and a single two-byte write that keep the
+    /// unused CONF bits.
+    async fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut Configuration),
+    ) -> Result<(), AS56Error<Self::Error>> {
+        let current = self.get_config().await?;
+        let mut config = current;
+        f(&mut config);
+        if config != current {
+            self.set_config(config).await?;
+        }
+        Ok(())
+    }
+
+    /// Sets the power mode, keeping the other configuration fields.
+    async fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.power_mode = mode).await
+    }
+
+    /// Sets the hysteresis, keeping the other configuration fields.
+    async fn set_hysteresis(
+        &mut self,
+        hysteresis: Hysteresis,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.hysteresis = hysteresis).await
+    }
+
+    /// Sets the output stage, keeping the other configuration fields.
+    async fn set_output_stage(&mut self, stage: OutputStage) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.output_stage = stage).await
+    }
+
+    /// Sets the PWM frequency, keeping the other configuration fields.
+    async fn set_pwm_frequency(
+        &mut self,
+        frequency: PwmFrequency,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.pwm_frequency = frequency).await
+    }
+
+    /// Sets the slow filter, keeping the other configuration fields.
+    async fn set_slow_filter(&mut self, filter: SlowFilter) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.slow_filter = filter).await
+    }
+
+    /// Sets the fast filter threshold, keeping the other configuration fields.
+    async fn set_fast_filter_threshold(
+        &mut self,
+        threshold: FastFilterThreshold,
+    ) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.fast_filter_threshold = threshold)
+            .await
+    }
+
+    /// Enables or disables the watchdog, keeping the other configuration fields.
+    async fn set_watchdog(&mut self, enabled: bool) -> Result<(), AS56Error<Self::Error>> {
+        self.modify_config(|c| c.watchdog = enabled).await
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::driver::AS5600Driver;
+    use crate::mock::AS56Mock;
+
+    #[test]
+    fn interface_is_dyn_compatible() {
+        let mut sensor = AS5600Driver::new(AS56Mock::new());
+        let sensor: &mut dyn AS5600Interface<Error = _> = &mut sensor;
+        sensor.set_zero_position(100).unwrap();
+        assert_eq!(sensor.get_zero_position().unwrap(), 100);
+    }
+
+    #[test]
+    fn typed_setters_write_counts() {
+        let mut sensor = AS5600Driver::new(AS56Mock::new());
+        sensor
+            .set_zero_position_counts(RawCounts::from_degrees(90.0))
+            .unwrap();
+        sensor.set_max_position_counts(RawCounts::MAX).unwrap();
+        sensor
+            .set_max_angle_counts(RawCounts::new(2048).unwrap())
+            .unwrap();
+        assert_eq!(sensor.get_zero_position().unwrap(), 1024);
+        assert_eq!(sensor.get_max_position().unwrap(), 4095);
+        assert_eq!(sensor.get_max_angle().unwrap(), 2048);
+    }
 }
@@ -1,3 +1,20 @@
+use crate::regs::regs;
+use core::fmt;
+
+/// Error returned when a register field holds a bit pattern without meaning.
+///
+/// Contains the rejected field value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidBits(pub u8);
+
+impl fmt::Display for InvalidBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid field value 0b{:b}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidBits {}
+
 /// Power consumption modes of the AS5600.
 ///
 /// Lower power modes reduce current consumption by increasing the sampling interval.
@@ -5,14 +22,29 @@
 pub enum PowerMode {
     /// No power saving, continuous sampling. (Current: ~6.5mA)
     Nominal = 0b00,
-    /// Low Power Mode 1 (Sampling: 1ms)
+    /// Low Power Mode 1 (Sampling: 5ms)
     LPM1 = 0b01,
-    /// Low Power Mode 2 (Sampling: 10ms)
+    /// Low Power Mode 2 (Sampling: 20ms)
     LPM2 = 0b10,
     /// Low Power Mode 3 (Sampling: 100ms)
     LPM3 = 0b11,
 }
 
+impl TryFrom<u8> for PowerMode {
+    type Error = InvalidBits;
+
+    /// Decodes the 2-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b00 => Ok(PowerMode::Nominal),
+            0b01 => Ok(PowerMode::LPM1),
+            0b10 => Ok(PowerMode::LPM2),
+            0b11 => Ok(PowerMode::LPM3),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// Hysteresis settings to suppress noise in the output.
 ///
 /// Defines the number of LSBs the position must change before the output is updated.
@@ -28,7 +60,24 @@ pub enum Hysteresis {
     Lsb3 = 0b11,
 }
 
+impl TryFrom<u8> for Hysteresis {
+    type Error = InvalidBits;
+
+    /// Decodes the 2-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b00 => Ok(Hysteresis::Off),
+            0b01 => Ok(Hysteresis::Lsb1),
+            0b10 => Ok(Hysteresis::Lsb2),
+            0b11 => Ok(Hysteresis::Lsb3),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// Output stage configuration for the OUT pin.
+///
+/// The OUTS pattern 0b11 is reserved.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputStage {
     /// Ratiometric analog output (0V to VDD).
@@ -39,6 +88,20 @@ pub enum OutputStage {
     PWM = 0b10,
 }
 
+impl TryFrom<u8> for OutputStage {
+    type Error = InvalidBits;
+
+    /// Decodes the 2-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b00 => Ok(OutputStage::AnalogFull),
+            0b01 => Ok(OutputStage::AnalogReduced),
+            0b10 => Ok(OutputStage::PWM),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// PWM signal frequency when using PWM output stage.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PwmFrequency {
@@ -52,6 +115,21 @@ pub enum PwmFrequency {
     Hz920 = 0b11,
 }
 
+impl TryFrom<u8> for PwmFrequency {
+    type Error = InvalidBits;
+
+    /// Decodes the 2-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b00 => Ok(PwmFrequency::Hz115),
+            0b01 => Ok(PwmFrequency::Hz230),
+            0b10 => Ok(PwmFrequency::Hz460),
+            0b11 => Ok(PwmFrequency::Hz920),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// Slow filter settings for noise reduction.
 ///
 /// Higher values mean more averaging and less noise, but higher step response time.
@@ -67,6 +145,21 @@ pub enum SlowFilter {
     X2 = 0b11,
 }
 
+impl TryFrom<u8> for SlowFilter {
+    type Error = InvalidBits;
+
+    /// Decodes the 2-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b00 => Ok(SlowFilter::X16),
+            0b01 => Ok(SlowFilter::X8),
+            0b10 => Ok(SlowFilter::X4),
+            0b11 => Ok(SlowFilter::X2),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// Fast filter threshold for adaptive filtering.
 ///
 /// If the position change exceeds this threshold, the slow filter is bypassed
@@ -91,6 +184,25 @@ pub enum FastFilterThreshold {
     Lsb10 = 0b111,
 }
 
+impl TryFrom<u8> for FastFilterThreshold {
+    type Error = InvalidBits;
+
+    /// Decodes the 3-bit register field value.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0b000 => Ok(FastFilterThreshold::SlowOnly),
+            0b001 => Ok(FastFilterThreshold::Lsb6),
+            0b010 => Ok(FastFilterThreshold::Lsb7),
+            0b011 => Ok(FastFilterThreshold::Lsb9),
+            0b100 => Ok(FastFilterThreshold::Lsb18),
+            0b101 => Ok(FastFilterThreshold::Lsb21),
+            0b110 => Ok(FastFilterThreshold::Lsb24),
+            0b111 => Ok(FastFilterThreshold::Lsb10),
+            _ => Err(InvalidBits(bits)),
+        }
+    }
+}
+
 /// Status of the magnetic system.
 ///
 /// Provides information about magnet detection and field strength.
@@ -104,10 +216,30 @@ pub struct MagnetStatus {
     pub too_strong: bool,
 }
 
+/// A coherent reading of all live (output) registers.
+///
+/// Returned by `read_snapshot()`, which fetches STATUS, RAW_ANGLE, ANGLE, AGC and
+/// MAGNITUDE together instead of one register per bus round-trip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Decoded magnet status.
+    pub status: MagnetStatus,
+    /// Raw value of the status register.
+    pub status_raw: u8,
+    /// Raw 12-bit angle from the Hall sensors.
+    pub raw_angle: u16,
+    /// 12-bit angle after applying all settings.
+    pub angle: u16,
+    /// Automatic Gain Control value.
+    pub agc: u8,
+    /// Magnitude of the magnetic field.
+    pub magnitude: u16,
+}
+
 /// Full configuration of the AS5600 chip.
 ///
 /// This struct maps to the CONF_HI and CONF_LO registers.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Configuration {
     /// Current power mode.
     pub power_mode: PowerMode,
@@ -123,6 +255,8 @@ pub struct Configuration {
     pub fast_filter_threshold: FastFilterThreshold,
     /// Enable/Disable the watchdog timer (auto-low-power after 1 minute of inactivity).
     pub watchdog: bool,
+    /// CONF_HI bits 7:6 (`0..=3`), which have no function but are written back as read.
+    pub unused_bits: u8,
 }
 
 impl Default for Configuration {
@@ -135,6 +269,137 @@ impl Default for Configuration {
             slow_filter: SlowFilter::X16,
             fast_filter_threshold: FastFilterThreshold::SlowOnly,
             watchdog: true,
+            unused_bits: 0,
+        }
+    }
+}
+
+/// Mask of the CONF_HI bits without function (7:6).
+pub(crate) const CONF_HI_UNUSED: u8 = 0xC0;
+
+impl Configuration {
+    /// Decodes the CONF_HI and CONF_LO register values.
+    ///
+    /// The reserved OUTS pattern is rejected. The unused CONF_HI bits are kept in
+    /// [`unused_bits`](Self::unused_bits), so every accepted pair is reproduced exactly
+    /// by [`to_registers`](Self::to_registers).
+    pub fn from_registers(hi: u8, lo: u8) -> Result<Self, InvalidConfig> {
+        let invalid = |register, mask| InvalidConfig {
+            register,
+            value: if register == regs::CONF_HI { hi } else { lo },
+            mask,
+        };
+        Ok(Self {
+            power_mode: PowerMode::try_from(lo & 0x03).map_err(|_| invalid(regs::CONF_LO, 0x03))?,
+            hysteresis: Hysteresis::try_from((lo >> 2) & 0x03)
+                .map_err(|_| invalid(regs::CONF_LO, 0x0C))?,
+            output_stage: OutputStage::try_from((lo >> 4) & 0x03)
+                .map_err(|_| invalid(regs::CONF_LO, 0x30))?,
+            pwm_frequency: PwmFrequency::try_from(lo >> 6)
+                .map_err(|_| invalid(regs::CONF_LO, 0xC0))?,
+            slow_filter: SlowFilter::try_from(hi & 0x03)
+                .map_err(|_| invalid(regs::CONF_HI, 0x03))?,
+            fast_filter_threshold: FastFilterThreshold::try_from((hi >> 2) & 0x07)
+                .map_err(|_| invalid(regs::CONF_HI, 0x1C))?,
+            watchdog: (hi & 0x20) != 0,
+            unused_bits: (hi & CONF_HI_UNUSED) >> 6,
+        })
+    }
+
+    /// Encodes the configuration into the `(CONF_HI, CONF_LO)` register values.
+    pub fn to_registers(&self) -> (u8, u8) {
+        let hi = ((self.unused_bits & 0x03) << 6)
+            | ((self.watchdog as u8) << 5)
+            | ((self.fast_filter_threshold as u8) << 2)
+            | (self.slow_filter as u8);
+
+        let lo = ((self.pwm_frequency as u8) << 6)
+            | ((self.output_stage as u8) << 4)
+            | ((self.hysteresis as u8) << 2)
+            | (self.power_mode as u8);
+
+        (hi, lo)
+    }
+}
+
+/// Error returned when a CONF register holds a reserved bit pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidConfig {
+    /// The register holding the pattern (CONF_HI or CONF_LO).
+    pub register: u8,
+    /// The value of that register.
+    pub value: u8,
+    /// The bits of the offending field.
+    pub mask: u8,
+}
+
+impl fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "reserved pattern 0x{:02X} (mask 0x{:02X}) in register 0x{:02X}",
+            self.value & self.mask,
+            self.mask,
+            self.register
+        )
+    }
+}
+
+impl core::error::Error for InvalidConfig {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conf_registers_round_trip_or_reject() {
+        let mut accepted = 0;
+        for word in 0..=0xFFFFu16 {
+            let [hi, lo] = word.to_be_bytes();
+            let result = Configuration::from_registers(hi, lo);
+            if (lo >> 4) & 0x03 == 0b11 {
+                let expected = InvalidConfig {
+                    register: regs::CONF_LO,
+                    value: lo,
+                    mask: 0x30,
+                };
+                assert_eq!(result, Err(expected), "CONF 0x{word:04X}");
+            } else {
+                assert_eq!(
+                    result.map(|c| c.to_registers()),
+                    Ok((hi, lo)),
+                    "CONF 0x{word:04X}"
+                );
+                accepted += 1;
+            }
+        }
+        // Every CONF_HI value times 192 valid CONF_LO values.
+        assert_eq!(accepted, 256 * 192);
+    }
+
+    /// Checks that `T` accepts exactly `0..valid` and rejects everything above.
+    fn check_field<T: TryFrom<u8, Error = InvalidBits>>(valid: u8, bits_of: fn(T) -> u8) {
+        for bits in 0..=u8::MAX {
+            match T::try_from(bits) {
+                Ok(value) => {
+                    assert!(bits < valid, "0b{bits:b} accepted");
+                    assert_eq!(bits_of(value), bits);
+                }
+                Err(InvalidBits(rejected)) => {
+                    assert!(bits >= valid, "0b{bits:b} rejected");
+                    assert_eq!(rejected, bits);
+                }
+            }
         }
     }
+
+    #[test]
+    fn fields_reject_values_outside_their_width() {
+        check_field(4, |v: PowerMode| v as u8);
+        check_field(4, |v: Hysteresis| v as u8);
+        check_field(3, |v: OutputStage| v as u8);
+        check_field(4, |v: PwmFrequency| v as u8);
+        check_field(4, |v: SlowFilter| v as u8);
+        check_field(8, |v: FastFilterThreshold| v as u8);
+    }
 }
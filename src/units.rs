@@ -0,0 +1,239 @@
+use crate::driver::MASK_12BIT;
+use core::f32::consts::TAU;
+
+/// Number of counts in one full 12-bit turn.
+const COUNTS_PER_TURN: u32 = 4096;
+
+/// Degrees per count in Q16.16 fixed point (`360 / 4096 * 65536`, exact).
+const DEGREES_Q16_PER_COUNT: u32 = 5760;
+
+macro_rules! counts_newtype {
+    ($name:ident) => {
+        impl $name {
+            /// Full-scale value (4095).
+            pub const MAX: Self = Self(MASK_12BIT);
+
+            /// Creates a value from 12-bit counts, or `None` if `counts` exceeds 4095.
+            pub const fn new(counts: u16) -> Option<Self> {
+                if counts <= MASK_12BIT {
+                    Some(Self(counts))
+                } else {
+                    None
+                }
+            }
+
+            /// Creates a value from the low 12 bits of `counts`, like the chip does.
+            pub const fn from_masked(counts: u16) -> Self {
+                Self(counts & MASK_12BIT)
+            }
+
+            /// Returns the 12-bit counts.
+            pub const fn counts(self) -> u16 {
+                self.0
+            }
+
+            /// Returns the value as a fraction of a full turn (`0.0..1.0`).
+            pub fn to_turns(self) -> f32 {
+                self.0 as f32 / COUNTS_PER_TURN as f32
+            }
+
+            /// Returns the value in degrees (`0.0..360.0`).
+            pub fn to_degrees(self) -> f32 {
+                self.0 as f32 * 360.0 / COUNTS_PER_TURN as f32
+            }
+
+            /// Returns the value in radians (`0.0..2π`).
+            pub fn to_radians(self) -> f32 {
+                self.0 as f32 * TAU / COUNTS_PER_TURN as f32
+            }
+
+            /// Returns the value in degrees as Q16.16 fixed point.
+            ///
+            /// The conversion is exact, so it suits MCUs without an FPU.
+            pub const fn to_degrees_q16(self) -> u32 {
+                self.0 as u32 * DEGREES_Q16_PER_COUNT
+            }
+
+            /// Returns the value as a fraction of a full turn in Q0.16 fixed point (exact).
+            pub const fn to_turns_q16(self) -> u16 {
+                self.0 << 4
+            }
+
+            /// Creates a value from degrees, rounded to the nearest count and wrapped to one turn.
+            pub fn from_degrees(degrees: f32) -> Self {
+                Self::from_turns(degrees / 360.0)
+            }
+
+            /// Creates a value from radians, rounded to the nearest count and wrapped to one turn.
+            pub fn from_radians(radians: f32) -> Self {
+                Self::from_turns(radians / TAU)
+            }
+
+            /// Creates a value from a fraction of a turn, rounded and wrapped to one turn.
+            pub fn from_turns(turns: f32) -> Self {
+                let scaled = turns * COUNTS_PER_TURN as f32;
+                let counts = if scaled >= 0.0 {
+                    (scaled + 0.5) as i64
+                } else {
+                    (scaled - 0.5) as i64
+                };
+                Self(counts.rem_euclid(COUNTS_PER_TURN as i64) as u16)
+            }
+
+            /// Creates a value from Q16.16 fixed-point degrees, rounded and wrapped to one turn.
+            pub const fn from_degrees_q16(degrees_q16: u32) -> Self {
+                let mut counts = degrees_q16 / DEGREES_Q16_PER_COUNT;
+                if degrees_q16 % DEGREES_Q16_PER_COUNT >= DEGREES_Q16_PER_COUNT / 2 {
+                    counts += 1;
+                }
+                Self((counts % COUNTS_PER_TURN) as u16)
+            }
+        }
+
+        impl TryFrom<u16> for $name {
+            type Error = u16;
+
+            /// Fails with the rejected value if it does not fit in 12 bits.
+            fn try_from(counts: u16) -> Result<Self, Self::Error> {
+                Self::new(counts).ok_or(counts)
+            }
+        }
+    };
+}
+
+/// A raw 12-bit shaft position, straight from the Hall sensors.
+///
+/// This is the unit of RAW_ANGLE and of the programming registers ZPOS, MPOS
+/// and MANG. Degree and radian conversions refer to the mechanical shaft angle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawCounts(u16);
+
+counts_newtype!(RawCounts);
+
+impl From<RawCounts> for u16 {
+    fn from(value: RawCounts) -> Self {
+        value.0
+    }
+}
+
+/// The scaled 12-bit ANGLE output.
+///
+/// ANGLE always spans 0..4095 over the programmed range (ZPOS to MPOS, or MANG),
+/// so the degree and radian conversions are relative to that range: they equal the
+/// shaft angle only when the full turn is used. Use [`Angle::to_range_degrees`]
+/// to convert against a narrower range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Angle(u16);
+
+counts_newtype!(Angle);
+
+impl Angle {
+    /// Returns the shaft angle in degrees from ZPOS, given the programmed range span.
+    pub fn to_range_degrees(self, span: RawCounts) -> f32 {
+        let span = if span.counts() == 0 {
+            COUNTS_PER_TURN
+        } else {
+            span.counts() as u32
+        };
+        self.0 as f32 * span as f32 * 360.0 / (COUNTS_PER_TURN * COUNTS_PER_TURN) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::PI;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn counts_are_limited_to_12_bits() {
+        assert_eq!(RawCounts::new(0), Some(RawCounts(0)));
+        assert_eq!(RawCounts::new(4095), Some(RawCounts::MAX));
+        assert_eq!(RawCounts::new(4096), None);
+        assert_eq!(Angle::try_from(4096), Err(4096));
+        assert_eq!(Angle::try_from(4095), Ok(Angle::MAX));
+        assert_eq!(RawCounts::from_masked(0xFABC).counts(), 0x0ABC);
+        assert_eq!(u16::from(RawCounts::MAX), 4095);
+    }
+
+    #[test]
+    fn converts_counts_to_units() {
+        let quarter = RawCounts(1024);
+        assert_eq!(quarter.to_degrees(), 90.0);
+        assert_eq!(quarter.to_turns(), 0.25);
+        assert!(close(quarter.to_radians(), PI / 2.0));
+
+        assert_eq!(RawCounts(0).to_degrees(), 0.0);
+        assert_eq!(RawCounts(0).to_radians(), 0.0);
+        // The largest count is one step short of a full turn.
+        assert_eq!(RawCounts::MAX.to_degrees(), 359.912_1);
+        assert_eq!(RawCounts::MAX.to_turns(), 4095.0 / 4096.0);
+        assert!(RawCounts::MAX.to_radians() < 2.0 * PI);
+    }
+
+    #[test]
+    fn converts_units_to_counts_with_rounding_and_wrapping() {
+        assert_eq!(RawCounts::from_degrees(0.0), RawCounts(0));
+        assert_eq!(RawCounts::from_degrees(90.0), RawCounts(1024));
+        // Half a count rounds up, just below rounds down.
+        assert_eq!(RawCounts::from_degrees(0.044), RawCounts(1));
+        assert_eq!(RawCounts::from_degrees(0.043), RawCounts(0));
+        // Rounding up from the last count wraps to zero.
+        assert_eq!(RawCounts::from_degrees(359.99), RawCounts(0));
+
+        // Negative and above one turn wrap around.
+        assert_eq!(RawCounts::from_degrees(-90.0), RawCounts(3072));
+        assert_eq!(RawCounts::from_degrees(-0.04), RawCounts(0));
+        assert_eq!(RawCounts::from_degrees(360.0), RawCounts(0));
+        assert_eq!(RawCounts::from_degrees(450.0), RawCounts(1024));
+        assert_eq!(RawCounts::from_degrees(-720.0), RawCounts(0));
+        assert_eq!(RawCounts::from_radians(-PI / 2.0), RawCounts(3072));
+        assert_eq!(RawCounts::from_radians(3.0 * PI), RawCounts(2048));
+        assert_eq!(RawCounts::from_turns(1.25), RawCounts(1024));
+        assert_eq!(RawCounts::from_turns(-0.25), RawCounts(3072));
+    }
+
+    #[test]
+    fn every_count_survives_a_round_trip() {
+        for counts in 0..=4095 {
+            let value = Angle(counts);
+            assert_eq!(Angle::from_degrees(value.to_degrees()), value);
+            assert_eq!(Angle::from_radians(value.to_radians()), value);
+            assert_eq!(Angle::from_turns(value.to_turns()), value);
+            assert_eq!(Angle::from_degrees_q16(value.to_degrees_q16()), value);
+        }
+    }
+
+    #[test]
+    fn converts_q16_fixed_point() {
+        assert_eq!(RawCounts(0).to_degrees_q16(), 0);
+        assert_eq!(RawCounts(1024).to_degrees_q16(), 90 << 16);
+        assert_eq!(RawCounts::MAX.to_degrees_q16(), 4095 * 5760);
+        assert_eq!(RawCounts(2048).to_turns_q16(), 0x8000);
+        assert_eq!(RawCounts::MAX.to_turns_q16(), 0xFFF0);
+
+        assert_eq!(RawCounts::from_degrees_q16(90 << 16), RawCounts(1024));
+        // Half a count (2880) rounds up.
+        assert_eq!(RawCounts::from_degrees_q16(2879), RawCounts(0));
+        assert_eq!(RawCounts::from_degrees_q16(2880), RawCounts(1));
+        // Above one turn wraps around.
+        assert_eq!(RawCounts::from_degrees_q16(360 << 16), RawCounts(0));
+        assert_eq!(RawCounts::from_degrees_q16(370 << 16), RawCounts(114));
+        assert_eq!(RawCounts::from_degrees_q16(u32::MAX), RawCounts(182));
+    }
+
+    #[test]
+    fn range_degrees_scale_with_the_span() {
+        // A zero span means the full turn.
+        assert_eq!(Angle(1024).to_range_degrees(RawCounts(0)), 90.0);
+        assert_eq!(Angle(2048).to_range_degrees(RawCounts(2048)), 90.0);
+        assert_eq!(Angle(0).to_range_degrees(RawCounts(1024)), 0.0);
+        assert!(close(
+            Angle::MAX.to_range_degrees(RawCounts(1024)),
+            4095.0 * 90.0 / 4096.0
+        ));
+    }
+}
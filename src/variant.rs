@@ -0,0 +1,35 @@
+use crate::regs::{AS5600L_DEFAULT_ADDR, DEFAULT_ADDR};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A chip variant of the AS5600 family.
+///
+/// Used as a type parameter of [`AS5600Driver`](crate::AS5600Driver) so that
+/// variant-specific registers are only reachable on the variants that have them.
+pub trait Variant: sealed::Sealed {
+    /// I2C address of a factory-fresh chip.
+    const DEFAULT_ADDR: u8;
+}
+
+/// The standard AS5600 with its fixed I2C address (0x36).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AS5600;
+
+/// The AS5600L with a programmable I2C address (default 0x40).
+///
+/// Adds the I2C_ADDR and I2C_UPDT registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AS5600L;
+
+impl sealed::Sealed for AS5600 {}
+impl sealed::Sealed for AS5600L {}
+
+impl Variant for AS5600 {
+    const DEFAULT_ADDR: u8 = DEFAULT_ADDR;
+}
+
+impl Variant for AS5600L {
+    const DEFAULT_ADDR: u8 = AS5600L_DEFAULT_ADDR;
+}
@@ -0,0 +1,361 @@
+use crate::error::AS56Error;
+use crate::multiturn::{COUNTS_PER_TURN, unwrap_delta};
+use crate::traits::AS5600Interface;
+use core::f32::consts::TAU;
+
+/// Converts a speed in raw counts per second to radians per second.
+pub fn counts_to_rad_per_sec(counts_per_sec: f32) -> f32 {
+    counts_per_sec * TAU / COUNTS_PER_TURN as f32
+}
+
+/// Converts a speed in raw counts per second to revolutions per minute.
+pub fn counts_to_rpm(counts_per_sec: f32) -> f32 {
+    counts_per_sec * 60.0 / COUNTS_PER_TURN as f32
+}
+
+/// Wraps a position in counts into `0..4096` (`f32::rem_euclid` needs `std`).
+fn wrap_turn(counts: f32) -> f32 {
+    let turn = COUNTS_PER_TURN as f32;
+    let wrapped = counts % turn;
+    if wrapped < 0.0 {
+        wrapped + turn
+    } else {
+        wrapped
+    }
+}
+
+/// Common interface of the shaft speed estimators.
+///
+/// Estimators consume raw angle samples tagged with a timestamp from any monotonic
+/// clock. The clock resolution is given once at construction as ticks per second,
+/// so microsecond timers, RTOS tick counters and `std::time::Instant` offsets all work.
+/// Timestamps may wrap around `u64::MAX`; a sample with the same timestamp as the
+/// previous one is ignored.
+pub trait VelocityEstimator {
+    /// Feeds a raw angle sample taken at `timestamp` (in clock ticks).
+    fn update(&mut self, raw: u16, timestamp: u64);
+
+    /// Returns the estimated speed in raw counts per second.
+    fn velocity(&self) -> f32;
+
+    /// Returns the estimated acceleration in raw counts per second squared.
+    fn acceleration(&self) -> f32;
+
+    /// Forgets all samples and estimates.
+    fn reset(&mut self);
+
+    /// Returns the estimated speed in radians per second.
+    fn rad_per_sec(&self) -> f32 {
+        counts_to_rad_per_sec(self.velocity())
+    }
+
+    /// Returns the estimated acceleration in radians per second squared.
+    fn rad_per_sec2(&self) -> f32 {
+        counts_to_rad_per_sec(self.acceleration())
+    }
+
+    /// Returns the estimated speed in revolutions per minute.
+    fn rpm(&self) -> f32 {
+        counts_to_rpm(self.velocity())
+    }
+
+    /// Reads the raw angle from `sensor`, feeds it and returns the speed in rad/s.
+    fn sample<S: AS5600Interface>(
+        &mut self,
+        sensor: &mut S,
+        timestamp: u64,
+    ) -> Result<f32, AS56Error<S::Error>> {
+        let raw = sensor.read_raw_angle()?;
+        self.update(raw, timestamp);
+        Ok(self.rad_per_sec())
+    }
+}
+
+/// Shared bookkeeping: previous sample and elapsed time between samples.
+#[derive(Debug, Clone, Copy)]
+struct SampleClock {
+    ticks_per_second: u32,
+    last: Option<(u16, u64)>,
+}
+
+impl SampleClock {
+    fn new(ticks_per_second: u32) -> Self {
+        Self {
+            ticks_per_second: ticks_per_second.max(1),
+            last: None,
+        }
+    }
+
+    /// Returns the unwrapped step in counts and the elapsed time in seconds
+    /// since the previous sample, or `None` for the first or a repeated sample.
+    fn advance(&mut self, raw: u16, timestamp: u64) -> Option<(f32, f32)> {
+        match self.last {
+            Some((_, last_ts)) if timestamp == last_ts => None,
+            Some((last_raw, last_ts)) => {
+                self.last = Some((raw, timestamp));
+                let ticks = timestamp.wrapping_sub(last_ts);
+                let dt = ticks as f32 / self.ticks_per_second as f32;
+                Some((unwrap_delta(last_raw, raw) as f32, dt))
+            }
+            None => {
+                self.last = Some((raw, timestamp));
+                None
+            }
+        }
+    }
+}
+
+/// Plain finite differencing between consecutive samples.
+///
+/// Has no lag, but amplifies quantization noise at high sample rates.
+#[derive(Debug, Clone, Copy)]
+pub struct Differencing {
+    clock: SampleClock,
+    velocity: f32,
+    acceleration: f32,
+}
+
+impl Differencing {
+    /// Creates an estimator for a clock running at `ticks_per_second`.
+    pub fn new(ticks_per_second: u32) -> Self {
+        Self {
+            clock: SampleClock::new(ticks_per_second),
+            velocity: 0.0,
+            acceleration: 0.0,
+        }
+    }
+}
+
+impl VelocityEstimator for Differencing {
+    fn update(&mut self, raw: u16, timestamp: u64) {
+        if let Some((step, dt)) = self.clock.advance(raw, timestamp) {
+            let velocity = step / dt;
+            self.acceleration = (velocity - self.velocity) / dt;
+            self.velocity = velocity;
+        }
+    }
+
+    fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.clock.ticks_per_second);
+    }
+}
+
+/// Finite differencing smoothed with an exponential moving average.
+///
+/// `alpha` in `(0, 1]` is the weight of the newest sample: smaller values
+/// give a smoother but slower estimate.
+#[derive(Debug, Clone, Copy)]
+pub struct ExponentialAverage {
+    clock: SampleClock,
+    alpha: f32,
+    velocity: f32,
+    acceleration: f32,
+}
+
+impl ExponentialAverage {
+    /// Creates an estimator for a clock running at `ticks_per_second`.
+    pub fn new(ticks_per_second: u32, alpha: f32) -> Self {
+        Self {
+            clock: SampleClock::new(ticks_per_second),
+            alpha: alpha.clamp(f32::EPSILON, 1.0),
+            velocity: 0.0,
+            acceleration: 0.0,
+        }
+    }
+}
+
+impl VelocityEstimator for ExponentialAverage {
+    fn update(&mut self, raw: u16, timestamp: u64) {
+        if let Some((step, dt)) = self.clock.advance(raw, timestamp) {
+            let velocity = self.velocity + self.alpha * (step / dt - self.velocity);
+            let acceleration = (velocity - self.velocity) / dt;
+            self.acceleration += self.alpha * (acceleration - self.acceleration);
+            self.velocity = velocity;
+        }
+    }
+
+    fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.clock.ticks_per_second, self.alpha);
+    }
+}
+
+/// Second-order tracking loop (PLL-style) estimator.
+///
+/// Keeps an internal position/velocity model and corrects it with the position
+/// error of every sample. The loop filters noise without the lag of a moving average
+/// and follows constant-speed motion with zero steady-state error.
+///
+/// The acceleration is the correction the loop applies to its velocity, `ki` times
+/// the position error. Under constant acceleration the loop settles at an error of
+/// `a / ki`, so it converges to the true acceleration, but it is noisier than the
+/// velocity because quantization errors are scaled by `ki`.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackingLoop {
+    clock: SampleClock,
+    kp: f32,
+    ki: f32,
+    position: f32,
+    velocity: f32,
+    acceleration: f32,
+}
+
+impl TrackingLoop {
+    /// Creates a loop with explicit proportional and integral gains.
+    pub fn new(ticks_per_second: u32, kp: f32, ki: f32) -> Self {
+        Self {
+            clock: SampleClock::new(ticks_per_second),
+            kp,
+            ki,
+            position: 0.0,
+            velocity: 0.0,
+            acceleration: 0.0,
+        }
+    }
+
+    /// Creates a critically damped loop with the given bandwidth in Hz.
+    pub fn with_bandwidth(ticks_per_second: u32, bandwidth_hz: f32) -> Self {
+        let omega = TAU * bandwidth_hz;
+        Self::new(ticks_per_second, 2.0 * omega, omega * omega)
+    }
+}
+
+impl VelocityEstimator for TrackingLoop {
+    fn update(&mut self, raw: u16, timestamp: u64) {
+        let first = self.clock.last.is_none();
+        match self.clock.advance(raw, timestamp) {
+            Some((_, dt)) => {
+                let half_turn = COUNTS_PER_TURN as f32 / 2.0;
+                let predicted = self.position + self.velocity * dt;
+                // Phase error wrapped to half a turn, like a PLL phase detector.
+                let error = wrap_turn(raw as f32 - predicted + half_turn) - half_turn;
+                self.acceleration = self.ki * error;
+                self.velocity += self.acceleration * dt;
+                self.position = wrap_turn(predicted + self.kp * error * dt);
+            }
+            None if first => self.position = raw as f32,
+            None => {}
+        }
+    }
+
+    fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.clock.ticks_per_second, self.kp, self.ki);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Microsecond clock, one sample per millisecond.
+    const TICKS_PER_SECOND: u32 = 1_000_000;
+    const PERIOD: u64 = 1_000;
+
+    /// Feeds `samples` positions moving `step` counts per millisecond from `raw`,
+    /// with timestamps starting at `start`.
+    fn run(estimator: &mut impl VelocityEstimator, raw: u16, step: i32, start: u64, samples: u32) {
+        for i in 0..samples {
+            let position = (raw as i32 + step * i as i32).rem_euclid(4096) as u16;
+            estimator.update(position, start.wrapping_add(i as u64 * PERIOD));
+        }
+    }
+
+    /// Checks all three estimators on the same input.
+    fn check(raw: u16, step: i32, start: u64) {
+        let expected = step as f32 * 1000.0;
+        let mut differencing = Differencing::new(TICKS_PER_SECOND);
+        let mut average = ExponentialAverage::new(TICKS_PER_SECOND, 0.2);
+        let mut tracking = TrackingLoop::with_bandwidth(TICKS_PER_SECOND, 20.0);
+        run(&mut differencing, raw, step, start, 2000);
+        run(&mut average, raw, step, start, 2000);
+        run(&mut tracking, raw, step, start, 2000);
+        for velocity in [
+            differencing.velocity(),
+            average.velocity(),
+            tracking.velocity(),
+        ] {
+            assert!(
+                (velocity - expected).abs() < 1.0,
+                "{velocity} != {expected}"
+            );
+        }
+        assert_eq!(differencing.acceleration(), 0.0);
+        assert!(average.acceleration().abs() < 1.0);
+        let acceleration = tracking.acceleration();
+        // f32 rounding of the position error, scaled by ki.
+        assert!(acceleration.abs() < 10.0, "{acceleration}");
+    }
+
+    #[test]
+    fn constant_speed_is_tracked() {
+        check(0, 1, 0);
+        check(0, 4, 0);
+        assert!((counts_to_rpm(4096.0) - 60.0).abs() < 1e-4);
+        assert!((counts_to_rad_per_sec(4096.0) - TAU).abs() < 1e-4);
+    }
+
+    #[test]
+    fn position_wrap_is_crossed_in_both_directions() {
+        // 2000 samples of 4 counts cross 0 at least once either way.
+        check(4000, 4, 0);
+        check(100, -4, 0);
+    }
+
+    #[test]
+    fn timer_wrap_is_crossed() {
+        check(0, 4, u64::MAX - 1000 * PERIOD);
+        check(0, -4, u64::MAX - 1000 * PERIOD + 1);
+    }
+
+    #[test]
+    fn repeated_timestamps_are_ignored() {
+        let mut estimator = Differencing::new(TICKS_PER_SECOND);
+        estimator.update(0, 0);
+        estimator.update(4, PERIOD);
+        estimator.update(100, PERIOD);
+        assert!((estimator.velocity() - 4000.0).abs() < 0.01);
+        estimator.reset();
+        estimator.update(100, PERIOD);
+        assert_eq!(estimator.velocity(), 0.0);
+    }
+
+    #[test]
+    fn tracking_loop_converges_to_constant_acceleration() {
+        let mut tracking = TrackingLoop::with_bandwidth(TICKS_PER_SECOND, 2.0);
+        // 2000 counts/s², starting at rest.
+        for i in 0..3000u64 {
+            let t = i as f32 / 1000.0;
+            let position = (1000.0 * t * t).round() as u32 % 4096;
+            tracking.update(position as u16, i * PERIOD);
+        }
+        let acceleration = tracking.acceleration();
+        assert!((acceleration - 2000.0).abs() < 200.0, "{acceleration}");
+        // The velocity lags behind by 2a/ω at constant acceleration.
+        let lag = 2.0 * 2000.0 / (TAU * 2.0);
+        let velocity = tracking.velocity();
+        assert!((velocity - (6000.0 - lag)).abs() < 20.0, "{velocity}");
+    }
+}